@@ -0,0 +1,46 @@
+//! Calibration documents of letters, digits and spelled-out digits.
+
+use aoc_runner::rng::Rng;
+
+use crate::SPELLED;
+
+fn line(r: &mut Rng) -> String {
+    let mut line = String::new();
+    for _ in 0..r.range(1, 6) {
+        match r.below(3) {
+            0 => {
+                let n = r.range(1, 4);
+                line.push_str(&r.string("abcdefghijklmnopqrstuvwxyz", n));
+            }
+            1 => {
+                let word = r.pick(&SPELLED);
+                line.push_str(word);
+            }
+            _ => line.push(char::from(b'0' + r.range(1, 9) as u8)),
+        }
+    }
+    // A plain digit, so that part 1 can read every line too.
+    let at = r.range(0, line.len());
+    line.insert(at, char::from(b'0' + r.range(1, 9) as u8));
+    line
+}
+
+/// `size` lines.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (0..size).map(|_| line(r)).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_lines_calibrate() {
+        let input = generate(50, &mut Rng::new(1));
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 50);
+        assert!(solve_part1(&lines).is_ok());
+        assert!(solve_part2(&lines).is_ok());
+    }
+}
@@ -1,33 +1,124 @@
-fn as_number(x: u8) -> u8 {
-    x - b'0'
-}
-
-pub fn solve_one(str: &str) -> u8 {
-    let chs = str.as_bytes();
-    let mut i = 0;
-    let mut k = str.len() - 1;
-    let mut res_a: Option<u8> = None;
-    let mut res_b: Option<u8> = None;
-    while res_a.is_none() || res_b.is_none() {
-        let a = chs[i];
-        let b = chs[k];
-        if a.is_ascii_digit() {
-            res_a = Some(as_number(a));
-        }
-        if b.is_ascii_digit() {
-            res_b = Some(as_number(b));
-        }
-        if res_a.is_none() {
-            i += 1;
+use std::fmt;
+
+pub mod generator;
+
+const SPELLED: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CalibrationError {
+    /// 1-based line in the document.
+    pub line: usize,
+    pub text: String,
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: no digit in {:?}", self.line, self.text)
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+/// Digits of `s` in order. With `spelled`, the words `one` to `nine` count
+/// too; they may share letters, so `eightwo` yields 8 and then 2.
+pub fn digits(s: &str, spelled: bool) -> impl Iterator<Item = u32> + '_ {
+    s.char_indices().filter_map(move |(i, ch)| {
+        if ch.is_ascii_digit() {
+            return ch.to_digit(10);
         }
-        if res_b.is_none() && k > 0 {
-            k -= 1;
+        if !spelled {
+            return None;
         }
-    }
+        SPELLED
+            .iter()
+            .position(|w| s[i..].starts_with(w))
+            .map(|d| d as u32 + 1)
+    })
+}
+
+/// First and last digit of the line as a two-digit number.
+pub fn calibration_value(s: &str, spelled: bool) -> Option<u32> {
+    let mut ds = digits(s, spelled);
+    let first = ds.next()?;
+    let last = ds.last().unwrap_or(first);
+    Some(first * 10 + last)
+}
 
-    res_a.unwrap() * 10 + res_b.unwrap()
+fn calibrate(strs: &[&str], spelled: bool) -> Result<u32, CalibrationError> {
+    strs.iter()
+        .enumerate()
+        .map(|(i, s)| {
+            calibration_value(s, spelled).ok_or_else(|| CalibrationError {
+                line: i + 1,
+                text: s.to_string(),
+            })
+        })
+        .sum()
 }
 
-pub fn solve(strs: Vec<&str>) -> u32 {
-    strs.iter().map(|x| solve_one(x) as u32).sum()
+pub fn solve_part1(strs: &[&str]) -> Result<u32, CalibrationError> {
+    calibrate(strs, false)
+}
+
+pub fn solve_part2(strs: &[&str]) -> Result<u32, CalibrationError> {
+    calibrate(strs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_fixture::{fixture, puzzle_tests};
+    use test_case::test_case;
+
+    #[test_case("1abc2", 12)]
+    #[test_case("pqr3stu8vwx", 38)]
+    #[test_case("a1b2c3d4e5f", 15)]
+    #[test_case("treb7uchet", 77)]
+    #[test_case("7", 77)]
+    fn test_calibration_value(s: &str, expected: u32) {
+        assert_eq!(calibration_value(s, false), Some(expected));
+    }
+
+    #[test_case("two1nine", 29)]
+    #[test_case("eightwothree", 83)]
+    #[test_case("abcone2threexyz", 13)]
+    #[test_case("xtwone3four", 24)]
+    #[test_case("4nineeightseven2", 42)]
+    #[test_case("zoneight234", 14)]
+    #[test_case("7pqrstsixteen", 76)]
+    #[test_case("eightwo", 82)]
+    fn test_spelled_calibration_value(s: &str, expected: u32) {
+        assert_eq!(calibration_value(s, true), Some(expected));
+    }
+
+    #[test]
+    fn test_part1() {
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()).unwrap());
+    }
+
+    #[test]
+    fn test_part2() {
+        let f = fixture!("spelled");
+        f.assert_part2(solve_part2(&f.lines()).unwrap());
+    }
+
+    #[test]
+    fn test_no_digits() {
+        assert_eq!(
+            solve_part1(&["1abc2", "abc", "3"]),
+            Err(CalibrationError {
+                line: 2,
+                text: "abc".to_string()
+            })
+        );
+        assert_eq!(calibration_value("one", false), None);
+    }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()).unwrap(), |f| solve_part2(
+        &f.lines()
+    )
+    .unwrap());
 }
@@ -1,19 +1,15 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::io;
 
-use _2023_1::solve;
+use aoc_runner::Day;
+
+use _2023_1::{generator, solve_part1, solve_part2};
 
 fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v = v.iter().map(String::as_str).collect();
-    let res = solve(v);
-    println!("{}", res);
-    Ok(())
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    day.try_part(1, |i| solve_part1(&i.lines()));
+    day.try_part(2, |i| solve_part2(&i.lines()));
+    day.finish()
 }
@@ -0,0 +1,41 @@
+//! Games of cubes drawn from a bag of the puzzle's colours.
+
+use aoc_runner::rng::Rng;
+
+use crate::COLOURS;
+
+fn round(r: &mut Rng) -> String {
+    let mut colours = COLOURS;
+    r.shuffle(&mut colours);
+    let n = r.range(1, colours.len());
+    colours[..n]
+        .iter()
+        .map(|c| format!("{} {}", r.range(1, 20), c))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `size` games of one to six rounds, each drawing up to 20 cubes of a colour.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (1..=size)
+        .map(|id| {
+            let rounds: Vec<String> = (0..r.range(1, 6)).map(|_| round(r)).collect();
+            format!("Game {}: {}", id, rounds.join("; "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_games_parse() {
+        let input = generate(40, &mut Rng::new(2));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(solve_part1(&lines).is_ok());
+        assert!(solve_part2(&lines).unwrap() > 0);
+    }
+}
@@ -1,136 +1,293 @@
-use std::cmp::max;
+use std::{collections::BTreeMap, fmt};
 
-#[derive(PartialEq, Debug)]
-struct Round {
-    red: u32,
-    green: u32,
-    blue: u32,
+pub mod generator;
+
+/// Cube counts by colour, used both for a single draw and for a bag.
+pub type Cubes = BTreeMap<String, u32>;
+
+/// Colours the puzzle's bag is known to hold.
+pub const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// The bag the Elf asks about in part 1.
+pub fn puzzle_bag() -> Cubes {
+    cubes(&[("red", 12), ("green", 13), ("blue", 14)])
 }
 
-impl Round {
-    fn empty() -> Round {
-        Round {
-            red: 0,
-            green: 0,
-            blue: 0,
-        }
-    }
+pub fn cubes(counts: &[(&str, u32)]) -> Cubes {
+    counts.iter().map(|(c, n)| (c.to_string(), *n)).collect()
 }
 
-#[derive(PartialEq, Debug)]
-struct Game {
-    id: u32,
-    rounds: Vec<Round>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Colours outside the known set are an error.
+    Strict,
+    /// Colours outside the known set are kept and listed in `Game::unknown`.
+    Lenient,
 }
 
-pub struct Bag {
-    pub red: u32,
-    pub green: u32,
-    pub blue: u32,
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Syntax(String),
+    UnknownColour(String),
 }
 
-fn parse_round(str: &str) -> Round {
-    str.split(",").fold(Round::empty(), |mut round, draw| {
-        let r: Option<Round> = (|| {
-            let mut s = draw.trim().split(" ");
-            let n: u32 = s.next()?.parse().ok()?;
-            match s.next()? {
-                "blue" => round.blue = n,
-                "red" => round.red = n,
-                "green" => round.green = n,
-                _ => {}
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line in the input.
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Syntax(s) => write!(f, "line {}: cannot parse {:?}", self.line, s),
+            ParseErrorKind::UnknownColour(c) => {
+                write!(f, "line {}: unknown colour {:?}", self.line, c)
             }
-            Some(round)
-        })();
-        r.expect("failed to parse draw")
-    })
+        }
+    }
 }
 
-fn parse_game(str: &str) -> Option<Game> {
-    let mut s = str.split(":");
-    let id: u32 = s.next()?.split(" ").last()?.parse().ok()?;
-    let rounds = s.next()?.split(";").map(parse_round).collect();
-    Some(Game { id, rounds })
+impl std::error::Error for ParseError {}
+
+/// A draw that needs more cubes of one colour than the bag holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 0-based round within the game.
+    pub round: usize,
+    pub colour: String,
+    pub drawn: u32,
+    pub available: u32,
 }
 
-fn is_round_possible(bag: &Bag, round: &Round) -> bool {
-    bag.red >= round.red && bag.blue >= round.blue && bag.green >= round.green
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "round {} drew {} {} but the bag holds {}",
+            self.round + 1,
+            self.drawn,
+            self.colour,
+            self.available
+        )
+    }
 }
 
-pub fn solve_one(bag: &Bag, str: &str) -> Option<u32> {
-    let g = parse_game(str).expect("Failed to parse game");
-    for round in g.rounds {
-        if !is_round_possible(bag, &round) {
-            return None;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Cubes>,
+    /// Colours outside the known set, in order of first appearance. Only
+    /// ever non-empty in lenient mode.
+    pub unknown: Vec<String>,
+}
+
+impl Game {
+    /// Parses `Game <id>: <n> <colour>, ...; ...` with `known` as the
+    /// accepted colours.
+    pub fn parse(s: &str, known: &[&str], mode: Mode) -> Result<Game, ParseErrorKind> {
+        let syntax = || ParseErrorKind::Syntax(s.to_string());
+        let (head, rounds) = s.split_once(':').ok_or_else(syntax)?;
+        let id = head
+            .strip_prefix("Game ")
+            .and_then(|id| id.trim().parse().ok())
+            .ok_or_else(syntax)?;
+
+        let mut unknown: Vec<String> = vec![];
+        let rounds = rounds
+            .split(';')
+            .map(|round| {
+                let mut cubes = Cubes::new();
+                for draw in round.split(',') {
+                    let (n, colour) = draw.trim().split_once(' ').ok_or_else(syntax)?;
+                    let n: u32 = n.parse().map_err(|_| syntax())?;
+                    if !known.contains(&colour) {
+                        match mode {
+                            Mode::Strict => {
+                                return Err(ParseErrorKind::UnknownColour(colour.to_string()))
+                            }
+                            Mode::Lenient if !unknown.iter().any(|c| c == colour) => {
+                                unknown.push(colour.to_string())
+                            }
+                            Mode::Lenient => {}
+                        }
+                    }
+                    *cubes.entry(colour.to_string()).or_insert(0) += n;
+                }
+                Ok(cubes)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Game {
+            id,
+            rounds,
+            unknown,
+        })
+    }
+
+    /// Every draw exceeding what `bag` holds; colours missing from the bag
+    /// count as zero.
+    pub fn violations(&self, bag: &Cubes) -> Vec<Violation> {
+        self.rounds
+            .iter()
+            .enumerate()
+            .flat_map(|(round, cubes)| {
+                cubes.iter().filter_map(move |(colour, drawn)| {
+                    let available = bag.get(colour).copied().unwrap_or(0);
+                    (*drawn > available).then(|| Violation {
+                        round,
+                        colour: colour.clone(),
+                        drawn: *drawn,
+                        available,
+                    })
+                })
+            })
+            .collect()
+    }
+
+    pub fn is_possible(&self, bag: &Cubes) -> bool {
+        self.violations(bag).is_empty()
+    }
+
+    /// The fewest cubes of each colour that make every round possible.
+    pub fn min_bag(&self) -> Cubes {
+        let mut bag = Cubes::new();
+        for (colour, n) in self.rounds.iter().flatten() {
+            let m = bag.entry(colour.clone()).or_insert(0);
+            *m = (*m).max(*n);
         }
+        bag
     }
-    Some(g.id)
 }
 
-pub fn min_bag_power(str: &str) -> u32 {
-    let g = parse_game(str).expect("Failed to parse game");
-
-    let mut b = Bag {
-        red: 0,
-        green: 0,
-        blue: 0,
-    };
-    for round in g.rounds {
-        b.red = max(round.red, b.red);
-        b.green = max(round.green, b.green);
-        b.blue = max(round.blue, b.blue);
-    }
-    bag_power(&b)
+/// Product of the counts of `colours` in `bag`, zero if one is missing.
+pub fn power(bag: &Cubes, colours: &[&str]) -> u32 {
+    colours
+        .iter()
+        .map(|c| bag.get(*c).copied().unwrap_or(0))
+        .product()
 }
 
-fn bag_power(b: &Bag) -> u32 {
-    b.red * b.green * b.blue
+pub fn parse_games(strs: &[&str], known: &[&str], mode: Mode) -> Result<Vec<Game>, ParseError> {
+    strs.iter()
+        .enumerate()
+        .map(|(i, s)| Game::parse(s, known, mode).map_err(|kind| ParseError { line: i + 1, kind }))
+        .collect()
 }
 
-pub fn solve(strs: &Vec<&str>) -> u32 {
-    let bag = Bag {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-    strs.iter().filter_map(|s| solve_one(&bag, s)).sum()
+pub fn solve_part1(strs: &[&str]) -> Result<u32, ParseError> {
+    let bag = puzzle_bag();
+    Ok(parse_games(strs, &COLOURS, Mode::Strict)?
+        .iter()
+        .filter(|g| g.is_possible(&bag))
+        .map(|g| g.id)
+        .sum())
 }
 
-pub fn solve_part2(strs: &Vec<&str>) -> u32 {
-    strs.iter().map(|s| min_bag_power(s)).sum()
+pub fn solve_part2(strs: &[&str]) -> Result<u32, ParseError> {
+    Ok(parse_games(strs, &COLOURS, Mode::Strict)?
+        .iter()
+        .map(|g| power(&g.min_bag(), &COLOURS))
+        .sum())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_fixture::{fixture, puzzle_tests};
+    use test_case::test_case;
+
+    #[test_case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", true, 48)]
+    #[test_case(
+        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+        true,
+        12
+    )]
+    #[test_case(
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        false,
+        1560
+    )]
+    #[test_case(
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        false,
+        630
+    )]
+    #[test_case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", true, 36)]
+    fn test_game(s: &str, possible: bool, power_of_min: u32) {
+        let g = Game::parse(s, &COLOURS, Mode::Strict).unwrap();
+        assert_eq!(g.is_possible(&puzzle_bag()), possible);
+        assert_eq!(power(&g.min_bag(), &COLOURS), power_of_min);
+    }
+
+    #[test]
+    fn test_example() {
+        let f = fixture!("example");
+        let input = f.lines();
+        f.assert_part1(solve_part1(&input).unwrap());
+        f.assert_part2(solve_part2(&input).unwrap());
+    }
 
     #[test]
     fn test_parse_game() {
         let game_str: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
 
-        let g = parse_game(game_str).unwrap();
+        let g = Game::parse(game_str, &COLOURS, Mode::Strict).unwrap();
 
         let expected = Game {
             id: 1,
             rounds: vec![
-                Round {
-                    red: 4,
-                    blue: 3,
-                    green: 0,
-                },
-                Round {
-                    red: 1,
-                    blue: 6,
-                    green: 2,
-                },
-                Round {
-                    red: 0,
-                    blue: 0,
-                    green: 2,
-                },
+                cubes(&[("blue", 3), ("red", 4)]),
+                cubes(&[("red", 1), ("green", 2), ("blue", 6)]),
+                cubes(&[("green", 2)]),
             ],
+            unknown: vec![],
         };
 
         assert_eq!(expected, g);
     }
+
+    #[test]
+    fn test_violations() {
+        let s = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+        let g = Game::parse(s, &COLOURS, Mode::Strict).unwrap();
+        let v = g.violations(&puzzle_bag());
+        assert_eq!(
+            v,
+            vec![Violation {
+                round: 0,
+                colour: "red".to_string(),
+                drawn: 20,
+                available: 12
+            }]
+        );
+        assert_eq!(v[0].to_string(), "round 1 drew 20 red but the bag holds 12");
+    }
+
+    #[test]
+    fn test_modes() {
+        let s = "Game 7: 2 red, 1 purple; 3 purple, 4 teal";
+        assert_eq!(
+            Game::parse(s, &COLOURS, Mode::Strict),
+            Err(ParseErrorKind::UnknownColour("purple".to_string()))
+        );
+
+        let g = Game::parse(s, &COLOURS, Mode::Lenient).unwrap();
+        assert_eq!(g.unknown, vec!["purple", "teal"]);
+        assert_eq!(
+            g.min_bag(),
+            cubes(&[("red", 2), ("purple", 3), ("teal", 4)])
+        );
+        assert!(!g.is_possible(&puzzle_bag()));
+        assert!(g.is_possible(&cubes(&[("red", 2), ("purple", 3), ("teal", 4)])));
+        assert_eq!(power(&g.min_bag(), &["purple", "teal"]), 12);
+
+        let err = parse_games(&["Game 1: 1 red", "Game x: 1 red"], &COLOURS, Mode::Strict);
+        assert_eq!(err.unwrap_err().line, 2);
+    }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()).unwrap(), |f| solve_part2(
+        &f.lines()
+    )
+    .unwrap());
 }
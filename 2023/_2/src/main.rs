@@ -1,21 +1,28 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::io;
 
-use _2023_2::{solve, solve_part2};
+use aoc_runner::Day;
+
+use _2023_2::{generator, parse_games, puzzle_bag, solve_part1, solve_part2, Mode, COLOURS};
 
 fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v = v.iter().map(String::as_str).collect();
-    let res = solve(&v);
-    println!("Part 1:{}", res);
-    let res = solve_part2(&v);
-    println!("Part 2:{}", res);
-    Ok(())
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    day.try_part(1, |i| solve_part1(&i.lines()));
+    day.try_part(2, |i| solve_part2(&i.lines()));
+    day.extras(|i| {
+        let bag = puzzle_bag();
+        match parse_games(&i.lines(), &COLOURS, Mode::Lenient) {
+            Ok(games) => {
+                for g in games {
+                    if let Some(why) = g.violations(&bag).first() {
+                        println!("Game {} is impossible: {}", g.id, why);
+                    }
+                }
+            }
+            Err(e) => println!("Cannot list impossible games: {}", e),
+        }
+    });
+    day.finish()
 }
@@ -0,0 +1,51 @@
+//! Engine schematics: numbers and symbols scattered over dots.
+
+use aoc_runner::rng::Rng;
+
+const SYMBOLS: &str = "*#+$/=%@&-";
+
+/// A `size` by `size` grid, about a third of it numbers and a tenth symbols,
+/// with `*` the most common symbol so that gears occur.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let mut grid = vec![vec!['.'; size]; size];
+    for row in grid.iter_mut() {
+        let mut x = 0;
+        while x < size {
+            if r.chance(0.1) {
+                let n = r.range(1, 3).min(size - x);
+                let digits = r.range(1, 9).to_string() + &r.string("0123456789", n - 1);
+                for (i, d) in digits.chars().enumerate() {
+                    row[x + i] = d;
+                }
+                x += n + 1;
+            } else {
+                if r.chance(0.1) {
+                    row[x] = match r.chance(0.5) {
+                        true => '*',
+                        false => *r.pick(&SYMBOLS.chars().collect::<Vec<_>>()),
+                    };
+                }
+                x += 1;
+            }
+        }
+    }
+    grid.into_iter()
+        .map(|l| l.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_schematic() {
+        let input = generate(60, &mut Rng::new(3));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(lines.iter().all(|l| l.len() == 60));
+        assert!(solve_part1(&lines) > 0);
+        assert!(solve_part2(&lines) > 0);
+    }
+}
@@ -1,26 +1,31 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
-
-use _2023_3::{solve_part1, solve_part2};
-
-fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v: Vec<&str> = v.iter().map(String::as_str).collect();
+use std::io;
 
-    let res = solve_part1(&v);
+use aoc_runner::Day;
 
-    println!("Part 1: {}", res);
-
-    let res = solve_part2(&v);
+use _2023_3::{
+    generator,
+    schematic::{Schematic, ANSI_OTHER, ANSI_PART},
+    solve_part1, solve_part2,
+};
 
-    println!("Part 2: {}", res);
+fn main() -> io::Result<()> {
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    day.part(1, |i| solve_part1(&i.lines()));
+    day.part(2, |i| solve_part2(&i.lines()));
+    day.extras(|i| {
+        let s = Schematic::parse(&i.lines());
+        for (n, count) in s.degree_histogram(b'*') {
+            println!("{} '*' touching {} numbers", count, n);
+        }
+        if std::env::args().any(|a| a == "--render") {
+            for line in s.render(ANSI_PART, ANSI_OTHER) {
+                println!("{}", line);
+            }
+        }
+    });
 
-    Ok(())
+    day.finish()
 }
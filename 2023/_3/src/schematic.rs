@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+/// Markers wrapping a number when rendering, e.g. ANSI colour codes.
+pub type Marker<'a> = (&'a str, &'a str);
+
+pub const ANSI_PART: Marker = ("\x1b[1;32m", "\x1b[0m");
+pub const ANSI_OTHER: Marker = ("\x1b[2;31m", "\x1b[0m");
+
+pub fn is_symbol(x: u8) -> bool {
+    !x.is_ascii_digit() && x != b'.'
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub y: usize,
+    /// Columns `start..end` hold the digits.
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub ch: u8,
+    pub x: usize,
+    pub y: usize,
+}
+
+pub struct Schematic {
+    lines: Vec<Vec<u8>>,
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// Indices into `numbers` touching each symbol, by symbol index.
+    adjacent: Vec<Vec<usize>>,
+    /// Whether each number touches any symbol.
+    is_part: Vec<bool>,
+}
+
+impl Schematic {
+    pub fn parse(strs: &[&str]) -> Schematic {
+        let lines: Vec<Vec<u8>> = strs.iter().map(|s| s.bytes().collect()).collect();
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        let mut at: Vec<Option<usize>> = vec![None; width * lines.len()];
+        for (y, line) in lines.iter().enumerate() {
+            let mut x = 0;
+            while x < line.len() {
+                if line[x].is_ascii_digit() {
+                    let start = x;
+                    let mut value = 0;
+                    while x < line.len() && line[x].is_ascii_digit() {
+                        value = value * 10 + (line[x] - b'0') as u32;
+                        x += 1;
+                    }
+                    numbers.push(Number {
+                        value,
+                        y,
+                        start,
+                        end: x,
+                    });
+                    continue;
+                }
+                if is_symbol(line[x]) {
+                    at[y * width + x] = Some(symbols.len());
+                    symbols.push(Symbol { ch: line[x], x, y });
+                }
+                x += 1;
+            }
+        }
+
+        let mut adjacent = vec![vec![]; symbols.len()];
+        let mut is_part = vec![false; numbers.len()];
+        for (i, n) in numbers.iter().enumerate() {
+            for y in n.y.saturating_sub(1)..(n.y + 2).min(lines.len()) {
+                for x in n.start.saturating_sub(1)..(n.end + 1).min(width) {
+                    if let Some(s) = at[y * width + x] {
+                        adjacent[s].push(i);
+                        is_part[i] = true;
+                    }
+                }
+            }
+        }
+        Schematic {
+            lines,
+            numbers,
+            symbols,
+            adjacent,
+            is_part,
+        }
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    /// Numbers touching at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(self.is_part.iter())
+            .filter_map(|(n, p)| p.then_some(n))
+    }
+
+    /// Symbols accepted by `pred`, each with the numbers touching it.
+    pub fn adjacency(&self, pred: impl Fn(u8) -> bool) -> Vec<(&Symbol, Vec<&Number>)> {
+        self.symbols
+            .iter()
+            .zip(self.adjacent.iter())
+            .filter(|(s, _)| pred(s.ch))
+            .map(|(s, ns)| (s, ns.iter().map(|i| &self.numbers[*i]).collect()))
+            .collect()
+    }
+
+    /// `ch` symbols touching exactly `n` numbers, e.g. the puzzle's gears
+    /// are `gears(b'*', 2)`.
+    pub fn gears(&self, ch: u8, n: usize) -> Vec<(&Symbol, Vec<&Number>)> {
+        self.adjacency(|c| c == ch)
+            .into_iter()
+            .filter(|(_, ns)| ns.len() == n)
+            .collect()
+    }
+
+    /// Number of `ch` symbols by how many numbers touch them.
+    pub fn degree_histogram(&self, ch: u8) -> BTreeMap<usize, usize> {
+        let mut hist = BTreeMap::new();
+        for (_, ns) in self.adjacency(|c| c == ch) {
+            *hist.entry(ns.len()).or_insert(0) += 1;
+        }
+        hist
+    }
+
+    /// The schematic with part numbers wrapped in `part` and all other
+    /// numbers in `other`.
+    pub fn render(&self, part: Marker, other: Marker) -> Vec<String> {
+        let mut out: Vec<String> = vec![];
+        let mut numbers = self.numbers.iter().zip(self.is_part.iter()).peekable();
+        for (y, line) in self.lines.iter().enumerate() {
+            let mut s = String::new();
+            let mut x = 0;
+            while let Some((n, is_part)) = numbers.next_if(|(n, _)| n.y == y) {
+                s.push_str(&String::from_utf8_lossy(&line[x..n.start]));
+                let (open, close) = if *is_part { part } else { other };
+                s.push_str(open);
+                s.push_str(&String::from_utf8_lossy(&line[n.start..n.end]));
+                s.push_str(close);
+                x = n.end;
+            }
+            s.push_str(&String::from_utf8_lossy(&line[x..]));
+            out.push(s);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_fixture::fixture;
+
+    #[test]
+    fn test_is_symbol() {
+        let cases = [
+            (b'*', true),
+            (b'#', true),
+            (b'+', true),
+            (b'$', true),
+            (b'2', false),
+            (b'.', false),
+            (b'@', true),
+            (b'/', true),
+            (b'%', true),
+        ];
+
+        for (s, e) in cases {
+            assert_eq!(is_symbol(s), e)
+        }
+    }
+
+    #[test]
+    fn test_index() {
+        let s = Schematic::parse(&fixture!("example").lines());
+        assert_eq!(s.numbers().len(), 10);
+        let others: Vec<u32> = s
+            .numbers()
+            .iter()
+            .filter(|n| !s.part_numbers().any(|p| p == *n))
+            .map(|n| n.value)
+            .collect();
+        assert_eq!(others, vec![114, 58]);
+
+        let gears: Vec<Vec<u32>> = s
+            .gears(b'*', 2)
+            .iter()
+            .map(|(_, ns)| ns.iter().map(|n| n.value).collect())
+            .collect();
+        assert_eq!(gears, vec![vec![467, 35], vec![755, 598]]);
+        let (sym, ns) = &s.gears(b'*', 1)[0];
+        assert_eq!((sym.x, sym.y, ns[0].value), (3, 4, 617));
+        assert_eq!(s.degree_histogram(b'*'), BTreeMap::from([(1, 1), (2, 2)]));
+
+        let plus = s.adjacency(|c| c == b'+' || c == b'$');
+        assert_eq!(plus.len(), 2);
+        assert_eq!(plus[0].1[0].value, 592);
+    }
+
+    #[test]
+    fn test_render() {
+        let s = Schematic::parse(&["467..114..", "...*......", "5....5"]);
+        assert_eq!(
+            s.render(("[", "]"), ("(", ")")),
+            vec!["[467]..(114)..", "...*......", "(5)....(5)"]
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! Two columns of location IDs, the right one reusing some of the left.
+
+use aoc_runner::rng::Rng;
+
+/// `size` pairs of five-digit IDs.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let left: Vec<usize> = (0..size).map(|_| r.range(10000, 99999)).collect();
+    (0..size)
+        .map(|i| {
+            let right = match r.chance(0.5) {
+                true => *r.pick(&left),
+                false => r.range(10000, 99999),
+            };
+            format!("{}   {}", left[i], right)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_lists() {
+        let input = generate(100, &mut Rng::new(1));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(solve_part1(&lines) > 0);
+        assert!(solve_part2(&lines) > 0);
+    }
+}
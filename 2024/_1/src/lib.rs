@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+pub mod generator;
+
 fn read_input(strs: &Vec<&str>) -> (Vec<u32>, Vec<u32>) {
     let mut left: Vec<u32> = vec![];
     let mut right: Vec<u32> = vec![];
@@ -60,24 +62,19 @@ pub fn solve_part2(strs: &Vec<&str>) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_part1() {
-        let case: Vec<&str> = vec!["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"];
-
-        let expected_res = 11;
-        let actual_res = solve_part1(&case);
-
-        assert_eq!(expected_res, actual_res);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_part2() {
-        let case: Vec<&str> = vec!["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"];
-
-        let expected_res = 31;
-        let actual_res = solve_part2(&case);
-
-        assert_eq!(expected_res, actual_res);
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,43 @@
+//! Topographic maps of hills that fall away from scattered summits.
+
+use aoc_runner::rng::Rng;
+
+/// A `size` by `size` map with a summit of height 9 in about one cell in
+/// forty. Other cells are 9 minus the distance to the nearest summit, or
+/// lower here and there to break trails.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let summits: Vec<(usize, usize)> = (0..(size * size / 40).max(1))
+        .map(|_| (r.below(size), r.below(size)))
+        .collect();
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let d = summits
+                        .iter()
+                        .map(|(sx, sy)| sx.abs_diff(x) + sy.abs_diff(y))
+                        .min()
+                        .unwrap();
+                    let h = 9usize.saturating_sub(d);
+                    let h = if r.chance(0.05) { r.below(h + 1) } else { h };
+                    char::from(b'0' + h as u8)
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_trails() {
+        let input = generate(40, &mut Rng::new(10));
+        let lines: Vec<&str> = input.lines().collect();
+        let (p1, p2) = (solve_part1(&lines), solve_part2(&lines));
+        assert!(0 < p1 && p1 < p2);
+    }
+}
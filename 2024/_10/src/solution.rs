@@ -122,42 +122,25 @@ pub fn solve_part2(strs: &[&str]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = "
-        89010123
-        78121874
-        87430965
-        96549874
-        45678903
-        32019012
-        01329801
-        10456732
-    ";
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect()
-    }
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_name() {
-        let input = read_input(INPUT);
-        let res = solve_part1(&input);
-        assert_eq!(res, 36);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_count_paths() {
-        let input = read_input(INPUT);
+        let input = fixture!("example").lines();
         count_uniq_paths((2, 0), (0, 3), &input);
     }
 
     #[test]
     fn test_example1() {
-        let input = read_input(INPUT);
-        let res = solve_part2(&input);
-        assert_eq!(res, 81);
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
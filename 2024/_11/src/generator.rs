@@ -0,0 +1,27 @@
+//! Rows of engraved stones.
+
+use aoc_runner::rng::Rng;
+
+/// `size` stones of up to seven digits, some of them 0.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let stones: Vec<String> = (0..size)
+        .map(|_| match r.chance(0.1) {
+            true => "0".to_string(),
+            false => r.range(1, 9_999_999).to_string(),
+        })
+        .collect();
+    stones.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_stones() {
+        let input = generate(20, &mut Rng::new(11));
+        assert_eq!(input.split(' ').count(), 20);
+        assert!(solve_part1(&input).unwrap() < solve_part2(&input).unwrap());
+    }
+}
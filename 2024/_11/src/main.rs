@@ -1,27 +1,37 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::io;
 
+use aoc_runner::Day;
+
+mod generator;
 mod solution;
+mod stones;
 
 use solution::{solve_part1, solve_part2};
+use stones::Stones;
 
 fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v: Vec<&str> = v.iter().map(String::as_str).collect();
-    println!("Part1:");
-    let res = solve_part1(v[0]);
-    println!("{}", res);
-
-    println!("Part2:");
-    let res = solve_part2(v[0]);
-    println!("{}", res);
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    day.try_part(1, |i| solve_part1(i.text()));
+    day.try_part(2, |i| solve_part2(i.text()));
+    day.extras(|i| {
+        let mut stones = Stones::parse(i.text());
+        match stones.evolve(75) {
+            Ok(distinct) => println!("Distinct values per blink:\n{:?}", distinct),
+            Err(e) => println!("Cannot evolve: {}", e),
+        }
+        match Stones::parse(i.text()).closure(1_000_000) {
+            Ok(Some(c)) => println!(
+                "Closure: {} values, complete after {} blinks",
+                c.values.len(),
+                c.depth
+            ),
+            Ok(None) => {}
+            Err(e) => println!("Cannot close: {}", e),
+        }
+    });
 
-    Ok(())
+    day.finish()
 }
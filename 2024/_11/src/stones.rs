@@ -0,0 +1,230 @@
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    fmt,
+};
+
+use aoc_math::{digits, split_digits};
+
+/// A stone whose replacement by a rule, or whose number of copies, exceeds
+/// `u64`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow(pub u64);
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Stone {} overflows u64", self.0)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// A rule returns the stones replacing `n`, or `None` if it does not apply.
+/// The first applicable rule in a list wins.
+pub type Rule = fn(u64) -> Option<Result<Vec<u64>, Overflow>>;
+
+pub fn zero_to_one(n: u64) -> Option<Result<Vec<u64>, Overflow>> {
+    (n == 0).then(|| Ok(vec![1]))
+}
+
+pub fn split_even_digits(n: u64) -> Option<Result<Vec<u64>, Overflow>> {
+    let d = digits(n);
+    if !d.is_multiple_of(2) {
+        return None;
+    }
+    let (high, low) = split_digits(n, d / 2);
+    Some(Ok(vec![high, low]))
+}
+
+pub fn multiply_2024(n: u64) -> Option<Result<Vec<u64>, Overflow>> {
+    Some(n.checked_mul(2024).map(|m| vec![m]).ok_or(Overflow(n)))
+}
+
+pub const PUZZLE_RULES: [Rule; 3] = [zero_to_one, split_even_digits, multiply_2024];
+
+/// Stones as a multiset: order never matters for counting, so each distinct
+/// number is stored once together with how many stones carry it.
+#[derive(Debug, Clone)]
+pub struct Stones {
+    counts: HashMap<u64, u64>,
+    rules: Vec<Rule>,
+}
+
+/// Every value reachable from the starting stones, and the number of blinks
+/// after which no new value appears.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Closure {
+    pub values: BTreeSet<u64>,
+    pub depth: usize,
+}
+
+impl Stones {
+    pub fn new(numbers: &[u64], rules: &[Rule]) -> Stones {
+        let mut counts = HashMap::new();
+        for n in numbers {
+            *counts.entry(*n).or_insert(0) += 1;
+        }
+        Stones {
+            counts,
+            rules: rules.to_vec(),
+        }
+    }
+
+    pub fn parse(s: &str) -> Stones {
+        let numbers: Vec<u64> = s.split_whitespace().flat_map(str::parse).collect();
+        Stones::new(&numbers, &PUZZLE_RULES)
+    }
+
+    fn apply(&self, n: u64) -> Result<Vec<u64>, Overflow> {
+        self.rules
+            .iter()
+            .find_map(|r| r(n))
+            .unwrap_or_else(|| Ok(vec![n]))
+    }
+
+    fn next(&self, counts: &HashMap<u64, u64>) -> Result<HashMap<u64, u64>, Overflow> {
+        let mut next = HashMap::with_capacity(counts.len());
+        for (n, cnt) in counts.iter() {
+            for x in self.apply(*n)? {
+                let c: &mut u64 = next.entry(x).or_insert(0);
+                *c = c.checked_add(*cnt).ok_or(Overflow(x))?;
+            }
+        }
+        Ok(next)
+    }
+
+    /// Leaves the stones as they were if a rule overflows.
+    pub fn blink(&mut self) -> Result<(), Overflow> {
+        self.counts = self.next(&self.counts)?;
+        Ok(())
+    }
+
+    /// Blinks `n` times. Rule sets that let the stones settle into a cycle
+    /// skip straight past its remaining laps, so `n` can be huge.
+    pub fn jump(&mut self, n: usize) -> Result<(), Overflow> {
+        // An overflow ends the walk as a state that steps to itself.
+        let end = aoc_sim::nth(Ok(self.counts.clone()), n, |c| {
+            c.clone().and_then(|c| self.next(&c))
+        });
+        self.counts = end?;
+        Ok(())
+    }
+
+    pub fn total(&self) -> Result<u64, Overflow> {
+        self.counts
+            .iter()
+            .try_fold(0u64, |acc, (n, c)| acc.checked_add(*c).ok_or(Overflow(*n)))
+    }
+
+    pub fn distinct(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Blinks `n` times and returns the number of distinct values after each blink.
+    pub fn evolve(&mut self, n: usize) -> Result<Vec<usize>, Overflow> {
+        (0..n)
+            .map(|_| {
+                self.blink()?;
+                Ok(self.distinct())
+            })
+            .collect()
+    }
+
+    /// Breadth-first walk over the values the rules can produce. Gives up
+    /// with `None` once more than `limit` values are seen, since an arbitrary
+    /// rule set need not have a finite closure.
+    pub fn closure(&self, limit: usize) -> Result<Option<Closure>, Overflow> {
+        let mut values: BTreeSet<u64> = self.counts.keys().copied().collect();
+        let mut queue: VecDeque<(u64, usize)> = values.iter().map(|n| (*n, 0)).collect();
+        let mut depth = 0;
+        while let Some((n, d)) = queue.pop_front() {
+            for x in self.apply(n)? {
+                if values.insert(x) {
+                    if values.len() > limit {
+                        return Ok(None);
+                    }
+                    depth = depth.max(d + 1);
+                    queue.push_back((x, d + 1));
+                }
+            }
+        }
+        Ok(Some(Closure { values, depth }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_even_digits() {
+        assert_eq!(split_even_digits(1000), Some(Ok(vec![10, 0])));
+        assert_eq!(split_even_digits(253000), Some(Ok(vec![253, 0])));
+        assert_eq!(split_even_digits(999), None);
+    }
+
+    #[test]
+    fn test_blink() {
+        let mut s = Stones::parse("0 1 10 99 999");
+        s.blink().unwrap();
+        assert_eq!(s.total(), Ok(7));
+        assert_eq!(s.distinct(), 5);
+
+        let mut s = Stones::parse("125 17");
+        assert_eq!(s.evolve(3), Ok(vec![3, 4, 5]));
+        s.evolve(3).unwrap();
+        assert_eq!(s.total(), Ok(22));
+    }
+
+    #[test]
+    fn test_jump() {
+        let mut s = Stones::parse("125 17");
+        s.jump(25).unwrap();
+        assert_eq!(s.total(), Ok(55312));
+
+        // Without the multiplying rule every stone ends up a single nonzero
+        // digit and the counts stop changing.
+        let mut s = Stones::new(&[0, 1234, 7], &[zero_to_one, split_even_digits]);
+        s.jump(usize::MAX).unwrap();
+        assert_eq!(s.total(), Ok(6));
+        assert_eq!(s.distinct(), 5);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // 17 digits, so the stone is multiplied rather than split.
+        let big = 10_000_000_000_000_000;
+        assert_eq!(multiply_2024(big), Some(Err(Overflow(big))));
+        assert_eq!(
+            multiply_2024(u64::MAX / 2024),
+            Some(Ok(vec![u64::MAX / 2024 * 2024]))
+        );
+
+        let mut s = Stones::parse(&format!("{} 1", big));
+        assert_eq!(s.blink(), Err(Overflow(big)));
+        assert_eq!(s.total(), Ok(2));
+        assert_eq!(s.jump(usize::MAX), Err(Overflow(big)));
+        assert_eq!(s.evolve(3), Err(Overflow(big)));
+        assert_eq!(s.closure(100).err(), Some(Overflow(big)));
+
+        // Too many copies: the 10s and the 0 all turn into 1s.
+        let mut s = Stones::parse("0");
+        s.counts.insert(10, u64::MAX);
+        assert!(s.total().is_err());
+        assert_eq!(s.blink(), Err(Overflow(1)));
+    }
+
+    #[test]
+    fn test_closure() {
+        let s = Stones::parse("0");
+        let c = s.closure(10_000).unwrap().unwrap();
+        assert!(c.values.contains(&2024));
+        assert!(c.values.contains(&4048));
+
+        let mut evolved = Stones::parse("0");
+        evolved.evolve(c.depth + 5).unwrap();
+        assert!(evolved.counts.keys().all(|n| c.values.contains(n)));
+
+        let s = Stones::new(&[1], &[|n| Some(Ok(vec![n + 1]))]);
+        assert_eq!(s.closure(100), Ok(None));
+    }
+}
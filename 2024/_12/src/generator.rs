@@ -0,0 +1,43 @@
+//! Garden plots: regions of plants grown around random seeds.
+
+use aoc_runner::rng::Rng;
+
+/// A `size` by `size` garden with a region around every tenth cell's worth
+/// of seeds, each cell taking the plant of its nearest seed. Plants repeat,
+/// so one plant can form several regions.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let seeds: Vec<(usize, usize, char)> = (0..(size * size / 10).max(1))
+        .map(|_| {
+            let plant = char::from(b'A' + r.below(26) as u8);
+            (r.below(size), r.below(size), plant)
+        })
+        .collect();
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    seeds
+                        .iter()
+                        .min_by_key(|(sx, sy, _)| sx.abs_diff(x) + sy.abs_diff(y))
+                        .unwrap()
+                        .2
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_garden() {
+        let input = generate(30, &mut Rng::new(12));
+        let lines: Vec<&str> = input.lines().collect();
+        let (p1, p2) = (solve_part1(&lines), solve_part2(&lines));
+        assert!(0 < p2 && p2 < p1);
+    }
+}
@@ -221,55 +221,11 @@ pub fn solve_part2(strs: &[&str]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const EXAMPLE0: &str = "
-        AAAA
-        BBCD
-        BBCC
-        EEEC
-    ";
-
-    const EXAMPLE1: &str = "
-        RRRRIICCFF
-        RRRRIICCCF
-        VVRRRCCFFF
-        VVRCCCJFFF
-        VVVVCJJCFE
-        VVIVCCJJEE
-        VVIIICJJEE
-        MIIIIIJJEE
-        MIIISIJEEE
-        MMMISSJEEE
-    ";
-
-    const EXAMPLE2: &str = "
-        AAAAAA
-        AAABBA
-        AAABBA
-        ABBAAA
-        ABBAAA
-        AAAAAA
-    ";
-
-    const EXAMPLE3: &str = "
-        AAAAAA
-        AAA..A
-        AAA..A
-        A..AAA
-        A..AAA
-        AAAAAA
-    ";
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect()
-    }
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_example0() {
-        let input = read_input(EXAMPLE0);
+        let input = fixture!("example0").lines();
         let w = World::new(&input);
         let res = scan_regions(&w);
         for r in res {
@@ -280,21 +236,21 @@ mod tests {
 
     #[test]
     fn test_example1() {
-        let input = read_input(EXAMPLE1);
-        let res = solve_part1(&input);
-        assert_eq!(res, 1930);
+        let f = fixture!("example1");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_example0_part2() {
-        let input = read_input(EXAMPLE0);
-        let res = solve_part2(&input);
-        assert_eq!(res, 80);
+        let f = fixture!("example0");
+        f.assert_part2(solve_part2(&f.lines()));
     }
 
     #[test]
     fn test_example2_part2() {
-        let input = read_input(EXAMPLE2);
+        let f = fixture!("example2");
+        let input = f.lines();
+        f.assert_part2(solve_part2(&input));
 
         let w = World::new(&input);
         let res = scan_regions(&w);
@@ -303,4 +259,6 @@ mod tests {
             dbg!(s);
         }
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
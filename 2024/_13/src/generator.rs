@@ -0,0 +1,43 @@
+//! Claw machines, about half of them winnable, a few with collinear buttons.
+
+use aoc_runner::rng::Rng;
+
+/// `size` machines.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let a = (r.range(10, 99), r.range(10, 99));
+            let b = match r.chance(0.05) {
+                true => {
+                    let k = r.range(2, 4);
+                    (a.0 * k, a.1 * k)
+                }
+                false => (r.range(10, 99), r.range(10, 99)),
+            };
+            let (na, nb) = (r.range(0, 100), r.range(0, 100));
+            let mut prize = (a.0 * na + b.0 * nb, a.1 * na + b.1 * nb);
+            if r.chance(0.5) {
+                prize.0 += r.range(1, 9);
+            }
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}",
+                a.0, a.1, b.0, b.1, prize.0, prize.1
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_machines() {
+        let input = generate(50, &mut Rng::new(13));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(solve_part1(&lines).unwrap() > 0);
+        solve_part2(&lines).unwrap();
+    }
+}
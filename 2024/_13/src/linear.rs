@@ -0,0 +1,236 @@
+use std::fmt;
+
+use aoc_math::{checked_det, checked_mul_add, ext_gcd, gcd};
+
+/// An intermediate value left the `i128` range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Intermediate value overflows i128")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Exact fraction kept in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Rational {
+        assert!(den != 0, "zero denominator");
+        let g = gcd(num, den).max(1) * den.signum();
+        Rational {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.num == 0
+    }
+
+    pub fn to_integer(self) -> Option<i128> {
+        (self.den == 1).then_some(self.num)
+    }
+
+    pub fn checked_sub(self, rhs: Rational) -> Option<Rational> {
+        Some(Rational::new(
+            checked_det(self.num, rhs.num, self.den, rhs.den)?,
+            self.den.checked_mul(rhs.den)?,
+        ))
+    }
+
+    pub fn checked_mul(self, rhs: Rational) -> Option<Rational> {
+        Some(Rational::new(
+            self.num.checked_mul(rhs.num)?,
+            self.den.checked_mul(rhs.den)?,
+        ))
+    }
+
+    /// Panics if `rhs` is zero.
+    pub fn checked_div(self, rhs: Rational) -> Option<Rational> {
+        Some(Rational::new(
+            self.num.checked_mul(rhs.den)?,
+            self.den.checked_mul(rhs.num)?,
+        ))
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational { num: n, den: 1 }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Solution {
+    Unique(Vec<Rational>),
+    Inconsistent,
+    /// The system is consistent but underdetermined; `rank` independent
+    /// equations remain after elimination.
+    Infinite {
+        rank: usize,
+    },
+}
+
+/// Solves `a * x = b` for a square integer matrix `a` by Gauss-Jordan
+/// elimination over rationals.
+pub fn solve(a: &[Vec<i128>], b: &[i128]) -> Result<Solution, Overflow> {
+    let n = a.len();
+    assert_eq!(n, b.len(), "matrix and right-hand side differ in size");
+    let mut m: Vec<Vec<Rational>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, rhs)| {
+            assert_eq!(row.len(), n, "matrix is not square");
+            row.iter()
+                .chain(std::iter::once(rhs))
+                .map(|v| Rational::from(*v))
+                .collect()
+        })
+        .collect();
+
+    let mut rank = 0;
+    for col in 0..n {
+        let Some(pivot) = (rank..n).find(|r| !m[*r][col].is_zero()) else {
+            continue;
+        };
+        m.swap(rank, pivot);
+        let p = m[rank][col];
+        for v in m[rank].iter_mut() {
+            *v = v.checked_div(p).ok_or(Overflow)?;
+        }
+        let pivot_row = m[rank].clone();
+        for (r, row) in m.iter_mut().enumerate() {
+            if r == rank || row[col].is_zero() {
+                continue;
+            }
+            let factor = row[col];
+            for (v, pv) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *v = factor
+                    .checked_mul(*pv)
+                    .and_then(|d| v.checked_sub(d))
+                    .ok_or(Overflow)?;
+            }
+        }
+        rank += 1;
+    }
+
+    if m[rank..].iter().any(|row| !row[n].is_zero()) {
+        return Ok(Solution::Inconsistent);
+    }
+    if rank < n {
+        return Ok(Solution::Infinite { rank });
+    }
+    Ok(Solution::Unique(m.into_iter().map(|row| row[n]).collect()))
+}
+
+/// Cheapest non-negative integer `(x, y)` with `p * x + q * y == target`,
+/// where `x` costs `cost_x` and `y` costs `cost_y`. `p` and `q` must not be
+/// negative. `Ok(None)` if there is no such pair.
+pub fn min_cost_combination(
+    p: i128,
+    q: i128,
+    target: i128,
+    cost_x: i128,
+    cost_y: i128,
+) -> Result<Option<(i128, i128)>, Overflow> {
+    assert!(p >= 0 && q >= 0, "negative step");
+    Ok(match (p, q) {
+        (0, 0) => (target == 0).then_some((0, 0)),
+        (0, q) => (target % q == 0 && target >= 0).then_some((0, target / q)),
+        (p, 0) => (target % p == 0 && target >= 0).then_some((target / p, 0)),
+        (p, q) => {
+            let (g, x0, y0) = ext_gcd(p, q);
+            if target % g != 0 {
+                return Ok(None);
+            }
+            // x = x0 + s * t, y = y0 - r * t for any integer t.
+            let x0 = x0.checked_mul(target / g).ok_or(Overflow)?;
+            let y0 = y0.checked_mul(target / g).ok_or(Overflow)?;
+            let (s, r) = (q / g, p / g);
+            let t_lo = -x0.div_euclid(s);
+            let t_hi = y0.div_euclid(r);
+            if t_lo > t_hi {
+                return Ok(None);
+            }
+            let slope = checked_det(cost_x, cost_y, r, s).ok_or(Overflow)?;
+            let t = if slope >= 0 { t_lo } else { t_hi };
+            Some((
+                checked_mul_add(s, t, x0).ok_or(Overflow)?,
+                checked_mul_add(-r, t, y0).ok_or(Overflow)?,
+            ))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rs(v: &[i128]) -> Vec<Rational> {
+        v.iter().map(|n| Rational::from(*n)).collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = vec![vec![94, 22], vec![34, 67]];
+        assert_eq!(
+            solve(&a, &[8400, 5400]),
+            Ok(Solution::Unique(rs(&[80, 40])))
+        );
+
+        let a = vec![vec![2, 1, -1], vec![-3, -1, 2], vec![-2, 1, 2]];
+        assert_eq!(
+            solve(&a, &[8, -11, -3]),
+            Ok(Solution::Unique(rs(&[2, 3, -1])))
+        );
+
+        let a = vec![vec![2, 0], vec![0, 3]];
+        assert_eq!(
+            solve(&a, &[1, 1]),
+            Ok(Solution::Unique(vec![
+                Rational::new(1, 2),
+                Rational::new(1, 3)
+            ]))
+        );
+
+        let a = vec![vec![1, 2], vec![2, 4]];
+        assert_eq!(solve(&a, &[3, 6]), Ok(Solution::Infinite { rank: 1 }));
+        assert_eq!(solve(&a, &[3, 7]), Ok(Solution::Inconsistent));
+
+        let big = 1 << 100;
+        let a = vec![vec![big, 1], vec![1, big]];
+        assert_eq!(solve(&a, &[1, 1]), Err(Overflow));
+    }
+
+    #[test]
+    fn test_min_cost_combination() {
+        assert_eq!(min_cost_combination(2, 4, 10, 3, 1), Ok(Some((1, 2))));
+        assert_eq!(min_cost_combination(2, 4, 10, 1, 3), Ok(Some((5, 0))));
+        assert_eq!(min_cost_combination(3, 5, 7, 1, 1), Ok(None));
+        assert_eq!(min_cost_combination(3, 5, 8, 1, 1), Ok(Some((1, 1))));
+        assert_eq!(min_cost_combination(0, 5, 10, 3, 1), Ok(Some((0, 2))));
+        assert_eq!(min_cost_combination(4, 6, 2, 1, 1), Ok(None));
+        assert_eq!(
+            min_cost_combination(3, 5, i128::MAX - 1, 1, 1),
+            Err(Overflow)
+        );
+    }
+}
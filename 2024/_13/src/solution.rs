@@ -1,3 +1,5 @@
+use crate::linear::{self, Overflow, Solution};
+
 #[derive(Debug)]
 struct Button {
     x: i64,
@@ -46,30 +48,136 @@ fn parse_game(game_str: &[&str]) -> Game {
     }
 }
 
-fn solve_game(g: Game) -> i64 {
-    let bn = (g.a.y * g.prize.0 - g.a.x * g.prize.1) / (g.a.y * g.b.x - g.a.x * g.b.y);
-    let an = (g.prize.1 - g.b.y * bn) / g.a.y;
-    if an * g.a.x + bn * g.b.x == g.prize.0 && an * g.a.y + bn * g.b.y == g.prize.1 {
-        an * 3 + bn
-    } else {
-        0
+const COST_A: i64 = 3;
+const COST_B: i64 = 1;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Presses {
+    pub a: i64,
+    pub b: i64,
+    pub cost: i64,
+}
+
+impl Presses {
+    fn new(a: i128, b: i128) -> Option<Presses> {
+        let a = i64::try_from(a).ok()?;
+        let b = i64::try_from(b).ok()?;
+        (a >= 0 && b >= 0).then_some(Presses {
+            a,
+            b,
+            cost: a * COST_A + b * COST_B,
+        })
     }
 }
 
-pub fn solve_part1(strs: &[&str]) -> i64 {
-    strs.split(|s| s.is_empty())
-        .map(parse_game)
-        .map(solve_game)
-        .sum()
+fn solve_game(g: &Game) -> Result<Option<Presses>, Overflow> {
+    let m = vec![
+        vec![g.a.x as i128, g.b.x as i128],
+        vec![g.a.y as i128, g.b.y as i128],
+    ];
+    let (px, py) = (g.prize.0 as i128, g.prize.1 as i128);
+    Ok(match linear::solve(&m, &[px, py])? {
+        Solution::Unique(v) => match (v[0].to_integer(), v[1].to_integer()) {
+            (Some(a), Some(b)) => Presses::new(a, b),
+            _ => None,
+        },
+        Solution::Inconsistent => None,
+        // Collinear buttons: both rows describe the same line, so either
+        // non-zero row alone determines the press counts.
+        Solution::Infinite { .. } => {
+            let (p, q, target) = if g.a.x != 0 || g.b.x != 0 {
+                (g.a.x, g.b.x, px)
+            } else {
+                (g.a.y, g.b.y, py)
+            };
+            linear::min_cost_combination(
+                p as i128,
+                q as i128,
+                target,
+                COST_A as i128,
+                COST_B as i128,
+            )?
+            .and_then(|(a, b)| Presses::new(a, b))
+        }
+    })
 }
 
-pub fn solve_part2(strs: &[&str]) -> i64 {
-    strs.split(|s| s.is_empty())
-        .map(|s| {
-            let mut g = parse_game(s);
-            g.prize.0 += 10000000000000;
-            g.prize.1 += 10000000000000;
-            solve_game(g)
-        })
-        .sum()
+fn total_cost(mut games: impl Iterator<Item = Game>) -> Result<i64, Overflow> {
+    games.try_fold(0, |acc, g| Ok(acc + solve_game(&g)?.map_or(0, |p| p.cost)))
+}
+
+pub fn solve_part1(strs: &[&str]) -> Result<i64, Overflow> {
+    total_cost(strs.split(|s| s.is_empty()).map(parse_game))
+}
+
+pub fn solve_part2(strs: &[&str]) -> Result<i64, Overflow> {
+    total_cost(strs.split(|s| s.is_empty()).map(|s| {
+        let mut g = parse_game(s);
+        g.prize.0 += 10000000000000;
+        g.prize.1 += 10000000000000;
+        g
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_fixture::{fixture, puzzle_tests};
+
+    #[test]
+    fn test_solve_game() {
+        let input = fixture!("example").lines();
+        let g = parse_game(&input[0..3]);
+        assert_eq!(
+            solve_game(&g),
+            Ok(Some(Presses {
+                a: 80,
+                b: 40,
+                cost: 280
+            }))
+        );
+        let g = parse_game(&input[4..7]);
+        assert_eq!(solve_game(&g), Ok(None));
+    }
+
+    #[test]
+    fn test_collinear() {
+        let g = parse_game(&[
+            "Button A: X+4, Y+2",
+            "Button B: X+2, Y+1",
+            "Prize: X=10, Y=5",
+        ]);
+        assert_eq!(
+            solve_game(&g),
+            Ok(Some(Presses {
+                a: 0,
+                b: 5,
+                cost: 5
+            }))
+        );
+        let g = parse_game(&[
+            "Button A: X+7, Y+1",
+            "Button B: X+14, Y+2",
+            "Prize: X=21, Y=3",
+        ]);
+        assert_eq!(
+            solve_game(&g),
+            Ok(Some(Presses {
+                a: 1,
+                b: 1,
+                cost: 4
+            }))
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()).unwrap());
+    }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()).unwrap(), |f| solve_part2(
+        &f.lines()
+    )
+    .unwrap());
 }
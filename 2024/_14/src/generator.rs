@@ -0,0 +1,32 @@
+//! Robots guarding the bathroom, on the puzzle's 101 by 103 tiles.
+
+use aoc_runner::rng::Rng;
+
+/// `size` robots.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let (x, y) = (r.below(101), r.below(103));
+            let (vx, vy) = (
+                r.range(0, 200) as isize - 100,
+                r.range(0, 200) as isize - 100,
+            );
+            format!("p={},{} v={},{}", x, y, vx, vy)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_part1;
+
+    #[test]
+    fn test_generated_robots() {
+        let input = generate(200, &mut Rng::new(14));
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 200);
+        assert!(solve_part1(&lines) > 0);
+    }
+}
@@ -1,7 +1,9 @@
-use std::{isize, ops::RangeBounds, thread::sleep, time::Duration, usize};
+use std::{collections::HashSet, isize, ops::RangeBounds, thread::sleep, time::Duration, usize};
 
-#[derive(Debug, Clone)]
-struct Robot {
+use aoc_math::advance_mod;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Robot {
     x: isize,
     y: isize,
     vx: isize,
@@ -35,26 +37,12 @@ fn parse_robot(s: &&str) -> Robot {
 }
 
 pub fn simulate_robot(r: &Robot, n: usize, max_x: isize, max_y: isize) -> Robot {
-    let mut x = r.x;
-    let mut y = r.y;
-    for _i in 0..n {
-        x += r.vx;
-        y += r.vy;
-        if x < 0 {
-            x += max_x;
-        } else if x >= max_x {
-            x -= max_x;
-        }
-
-        if y < 0 {
-            y += max_y;
-        } else if y >= max_y {
-            y -= max_y;
-        }
-    }
+    let advance = |p: isize, v: isize, m: isize| {
+        advance_mod(p as i128, v as i128, n as i128, m as i128) as isize
+    };
     Robot {
-        x,
-        y,
+        x: advance(r.x, r.vx, max_x),
+        y: advance(r.y, r.vy, max_y),
         vx: r.vx,
         vy: r.vy,
     }
@@ -87,29 +75,32 @@ pub fn solve(robots: &[Robot], n: usize, max_x: isize, max_y: isize) -> isize {
     per_q.iter().product()
 }
 
-fn draw_robots(robots: &[Robot], max_x: usize, max_y: usize) {
+pub fn render_robots(robots: &[Robot], max_x: usize, max_y: usize) -> Vec<String> {
     let mut world: Vec<Vec<char>> = (0..max_y)
         .map(|_i| std::iter::repeat('.').take(max_x).collect())
         .collect();
     for r in robots {
         world[r.y as usize][r.x as usize] = '#';
     }
-
-    for line in world {
-        println!("{}", String::from_iter(line));
-    }
+    world.into_iter().map(String::from_iter).collect()
 }
 
-fn simulate_and_draw(robots: &[Robot], max_n: usize, max_x: isize, max_y: isize) {
-    let mut new_robots: Vec<Robot> = robots.to_vec();
-    for i in 0..max_n {
-        println!("Gen {}", i);
-        new_robots = new_robots
-            .iter()
-            .map(|r| simulate_robot(r, 1, max_x, max_y))
-            .collect::<Vec<Robot>>();
-        draw_robots(&new_robots, max_x as usize, max_y as usize);
-    }
+/// The first second of the robots' cycle at which no two of them share a
+/// tile, and their arrangement then. The picture is drawn with one robot
+/// per tile, while random noise almost always stacks a few.
+pub fn picture(robots: &[Robot], max_x: isize, max_y: isize) -> Option<(usize, Vec<Robot>)> {
+    let cycle = aoc_sim::brent(robots.to_vec(), |robots| {
+        Some(
+            robots
+                .iter()
+                .map(|r| simulate_robot(r, 1, max_x, max_y))
+                .collect(),
+        )
+    });
+    cycle.states.into_iter().enumerate().find(|(_, robots)| {
+        let tiles: HashSet<(isize, isize)> = robots.iter().map(|r| (r.x, r.y)).collect();
+        tiles.len() == robots.len()
+    })
 }
 
 pub fn parse_robots(strs: &[&str]) -> Vec<Robot> {
@@ -123,44 +114,21 @@ pub fn solve_part1(strs: &[&str]) -> isize {
 
 pub fn solve_part2(strs: &[&str]) -> usize {
     let robots = parse_robots(strs);
-    simulate_and_draw(&robots, 10000, 101, 103);
-    0
+    picture(&robots, 101, 103).expect("Robots always overlap").0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const EXAMPLE0: &str = "
-        p=0,4 v=3,-3
-        p=6,3 v=-1,-3
-        p=10,3 v=-1,2
-        p=2,0 v=2,-1
-        p=0,0 v=1,3
-        p=3,0 v=-2,-2
-        p=7,6 v=-1,-3
-        p=3,0 v=-1,-2
-        p=9,3 v=2,3
-        p=7,3 v=-1,2
-        p=2,4 v=2,-3
-        p=9,5 v=-3,-3
-    ";
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect()
-    }
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_name() {
-        let input = read_input(EXAMPLE0);
-        let robots = parse_robots(&input);
+        let f = fixture!("example");
+        let robots = parse_robots(&f.lines());
 
-        let res = solve(&robots, 100, 11, 7);
-        dbg!(res);
-        assert_eq!(12, res);
+        let res = solve(&robots, 100, f.param("width"), f.param("height"));
+        f.assert_part1(res);
     }
 
     #[test]
@@ -172,10 +140,35 @@ mod tests {
             vy: 2,
         };
 
-        draw_robots(&vec![robot.clone()], 11, 7);
+        assert_eq!(render_robots(&vec![robot.clone()], 11, 7)[3], ".......#...");
 
         let new_robot = simulate_robot(&robot, 5, 11, 7);
 
-        draw_robots(&vec![new_robot.clone()], 11, 7);
+        assert_eq!(
+            render_robots(&vec![new_robot.clone()], 11, 7)[6],
+            "..#........"
+        );
     }
+
+    #[test]
+    fn test_picture() {
+        // The robots share a tile at every even second.
+        let robots = parse_robots(&["p=0,0 v=1,0", "p=0,0 v=3,0"]);
+        let (i, frame) = picture(&robots, 4, 1).unwrap();
+        assert_eq!(i, 1);
+        assert_eq!(render_robots(&frame, 4, 1), [".#.#"]);
+
+        let robots = parse_robots(&["p=0,0 v=1,0", "p=0,0 v=1,0"]);
+        assert_eq!(picture(&robots, 4, 1), None);
+    }
+
+    puzzle_tests!(
+        |f| solve(
+            &parse_robots(&f.lines()),
+            100,
+            f.param("width"),
+            f.param("height")
+        ),
+        |f| solve_part2(&f.lines())
+    );
 }
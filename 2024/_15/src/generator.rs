@@ -0,0 +1,49 @@
+//! Warehouses of boxes and walls, and the robot's moves.
+
+use aoc_runner::rng::Rng;
+
+/// A `size` by `size` warehouse walled in, a fifth of it boxes and a
+/// twentieth walls, and ten moves per cell in lines of 70.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let size = size.max(3);
+    let mut grid: Vec<Vec<char>> = (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    if x == 0 || y == 0 || x == size - 1 || y == size - 1 || r.chance(0.05) {
+                        '#'
+                    } else if r.chance(0.2) {
+                        'O'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let (x, y) = (r.range(1, size - 2), r.range(1, size - 2));
+    grid[y][x] = '@';
+
+    let moves = r.string("<>^v", size * size * 10);
+    let map: Vec<String> = grid.into_iter().map(|l| l.into_iter().collect()).collect();
+    let moves: Vec<String> = moves
+        .as_bytes()
+        .chunks(70)
+        .map(|c| String::from_utf8(c.to_vec()).unwrap())
+        .collect();
+    format!("{}\n\n{}", map.join("\n"), moves.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_warehouse() {
+        let input = generate(20, &mut Rng::new(15));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(solve_part1(&lines) > 0);
+        assert!(solve_part2(&lines) > 0);
+    }
+}
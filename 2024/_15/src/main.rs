@@ -1,28 +1,39 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use aoc_runner::Day;
+use std::{env, io};
 
+mod generator;
+mod push;
 mod solution;
 
-use solution::{solve_part1, solve_part2};
+use solution::{parse_input, solve_part1, solve_part2, Layout};
 
 fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v: Vec<&str> = v.iter().map(String::as_str).collect();
-    println!("Part1:");
-    let res = solve_part1(&v);
-    println!("{}", res);
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    day.part(1, |i| solve_part1(&i.lines()));
+    day.part(2, |i| solve_part2(&i.lines()));
 
-    let res = solve_part2(&v);
+    // `--rewind N` shows the wide warehouse N moves before the end.
+    let args: Vec<String> = env::args().collect();
+    if let Some(i) = args.iter().position(|a| a == "--rewind") {
+        let n: usize = args
+            .get(i + 1)
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "--rewind needs a count"))?;
+        day.extras(move |i| {
+            let mut w = parse_input(&i.lines(), Layout::Wide);
+            w.run();
+            for _ in 0..n {
+                w.warehouse.undo();
+            }
+            println!("{} moves before the end:", n);
+            for line in w.warehouse.render() {
+                println!("{}", line);
+            }
+        });
+    }
 
-    println!("Part2:");
-    println!("{}", res);
-
-    Ok(())
+    day.finish()
 }
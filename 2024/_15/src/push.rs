@@ -0,0 +1,212 @@
+//! Sokoban-style pushing of rigid objects between walls.
+//!
+//! Objects are arbitrary sets of cells. Pushing one moves every object it
+//! transitively runs into, all at once or not at all.
+
+pub type Pos = (usize, usize);
+pub type Dir = (isize, isize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Object {
+    /// Anchor the cells are relative to, conventionally the top-left one.
+    pub pos: Pos,
+    /// Offsets from `pos` with the glyph drawn there.
+    pub cells: Vec<(Pos, char)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    width: usize,
+    height: usize,
+    walls: Vec<bool>,
+    /// Object occupying each cell.
+    at: Vec<Option<usize>>,
+    objects: Vec<Object>,
+    /// Objects moved by each push, for `undo`.
+    history: Vec<(Vec<usize>, Dir)>,
+}
+
+impl Warehouse {
+    pub fn new(width: usize, height: usize) -> Warehouse {
+        Warehouse {
+            width,
+            height,
+            walls: vec![false; width * height],
+            at: vec![None; width * height],
+            objects: vec![],
+            history: vec![],
+        }
+    }
+
+    pub fn add_wall(&mut self, (x, y): Pos) {
+        self.walls[y * self.width + x] = true;
+    }
+
+    /// Places an object and returns its id. Panics if it overlaps a wall or
+    /// another object.
+    pub fn add(&mut self, pos: Pos, cells: Vec<(Pos, char)>) -> usize {
+        let id = self.objects.len();
+        self.objects.push(Object { pos, cells });
+        for (x, y) in self.cells(id).collect::<Vec<_>>() {
+            let i = y * self.width + x;
+            assert!(
+                !self.walls[i] && self.at[i].is_none(),
+                "Object {} overlaps ({}, {})",
+                id,
+                x,
+                y
+            );
+            self.at[i] = Some(id);
+        }
+        id
+    }
+
+    pub fn objects(&self) -> &[Object] {
+        &self.objects
+    }
+
+    fn cells(&self, id: usize) -> impl Iterator<Item = Pos> + '_ {
+        let (x, y) = self.objects[id].pos;
+        self.objects[id]
+            .cells
+            .iter()
+            .map(move |((dx, dy), _)| (x + dx, y + dy))
+    }
+
+    fn shift(&self, (x, y): Pos, (dx, dy): Dir) -> Option<Pos> {
+        let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|y| *y < self.height)?;
+        Some((x, y))
+    }
+
+    /// Every object that moves when `id` is pushed by `dir`, `id` first, or
+    /// `None` if one of them would hit a wall or the edge.
+    fn push_set(&self, id: usize, dir: Dir) -> Option<Vec<usize>> {
+        let mut seen = vec![false; self.objects.len()];
+        seen[id] = true;
+        let mut set = vec![id];
+        let mut i = 0;
+        while i < set.len() {
+            let cur = set[i];
+            for p in self.cells(cur) {
+                let (x, y) = self.shift(p, dir)?;
+                let j = y * self.width + x;
+                if self.walls[j] {
+                    return None;
+                }
+                if let Some(o) = self.at[j].filter(|o| !seen[*o]) {
+                    seen[o] = true;
+                    set.push(o);
+                }
+            }
+            i += 1;
+        }
+        Some(set)
+    }
+
+    fn apply(&mut self, ids: &[usize], dir: Dir) {
+        for id in ids {
+            for (x, y) in self.cells(*id).collect::<Vec<_>>() {
+                self.at[y * self.width + x] = None;
+            }
+        }
+        for id in ids {
+            let pos = self.objects[*id].pos;
+            self.objects[*id].pos = self.shift(pos, dir).expect("Checked push");
+            for (x, y) in self.cells(*id).collect::<Vec<_>>() {
+                self.at[y * self.width + x] = Some(*id);
+            }
+        }
+    }
+
+    /// Pushes `id` one cell along `dir` together with everything in its way.
+    /// Returns whether anything moved; blocked pushes are recorded too, so
+    /// each push is undone by exactly one `undo`.
+    pub fn push(&mut self, id: usize, dir: Dir) -> bool {
+        let ids = self.push_set(id, dir).unwrap_or_default();
+        self.apply(&ids, dir);
+        let moved = !ids.is_empty();
+        self.history.push((ids, dir));
+        moved
+    }
+
+    /// Reverts the last push. Returns `false` if there is none.
+    pub fn undo(&mut self) -> bool {
+        let Some((ids, (dx, dy))) = self.history.pop() else {
+            return false;
+        };
+        self.apply(&ids, (-dx, -dy));
+        true
+    }
+
+    pub fn render(&self) -> Vec<String> {
+        let mut grid: Vec<Vec<char>> = (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| match self.walls[y * self.width + x] {
+                        true => '#',
+                        false => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+        for o in self.objects.iter() {
+            for ((dx, dy), ch) in o.cells.iter() {
+                grid[o.pos.1 + dy][o.pos.0 + dx] = *ch;
+            }
+        }
+        grid.into_iter().map(|l| l.into_iter().collect()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ```text
+    /// ......
+    /// .L....
+    /// .LLb.#
+    /// .a....
+    /// ```
+    fn warehouse() -> (Warehouse, usize, usize, usize) {
+        let mut w = Warehouse::new(6, 4);
+        w.add_wall((5, 2));
+        let l = w.add((1, 1), vec![((0, 0), 'L'), ((0, 1), 'L'), ((1, 1), 'L')]);
+        let b = w.add((3, 2), vec![((0, 0), 'b')]);
+        let a = w.add((1, 3), vec![((0, 0), 'a')]);
+        (w, l, b, a)
+    }
+
+    #[test]
+    fn test_push_chain() {
+        let (mut w, l, b, a) = warehouse();
+        assert!(w.push(a, (0, -1)));
+        assert!(w.push(l, (1, 0)));
+        assert_eq!(w.render(), vec!["..L...", "..LL..", ".a.b.#", "......"]);
+        // b would push L off the top edge.
+        assert!(!w.push(b, (0, -1)));
+        assert!(w.push(a, (1, 0)));
+        assert!(w.push(a, (1, 0)));
+        assert_eq!(w.objects()[b].pos, (4, 2));
+        assert!(!w.push(a, (1, 0)));
+        assert_eq!(w.render(), vec!["..L...", "..LL..", "...ab#", "......"]);
+    }
+
+    #[test]
+    fn test_blocked_shape_and_undo() {
+        let (mut w, l, b, _) = warehouse();
+        let start = w.render();
+        // The foot of the L pushes b.
+        assert!(w.push(l, (1, 0)));
+        assert_eq!((w.objects()[l].pos, w.objects()[b].pos), ((2, 1), (4, 2)));
+        // b hits the wall, so neither moves.
+        assert!(!w.push(l, (1, 0)));
+        assert_eq!((w.objects()[l].pos, w.objects()[b].pos), ((2, 1), (4, 2)));
+
+        assert!(w.undo());
+        assert!(w.undo());
+        assert!(!w.undo());
+        assert_eq!(w.render(), start);
+    }
+}
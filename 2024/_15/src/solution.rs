@@ -1,4 +1,6 @@
-use std::collections::{HashSet, LinkedList};
+use std::collections::LinkedList;
+
+use crate::push::{Dir, Warehouse};
 
 #[derive(Debug)]
 enum Mov {
@@ -31,446 +33,169 @@ impl From<Mov> for (isize, isize) {
     }
 }
 
-fn parse_input(strs: &[&str]) -> World {
+/// How the map's tiles are laid out in the warehouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// As drawn, with 1x1 `O` boxes.
+    Single,
+    /// Every tile twice as wide, with 2x1 `[]` boxes.
+    Wide,
+}
+
+impl Layout {
+    fn expand(self, c: char) -> String {
+        match (self, c) {
+            (Layout::Single, _) => c.to_string(),
+            (Layout::Wide, '@') => "@.".to_string(),
+            (Layout::Wide, 'O') => "[]".to_string(),
+            (Layout::Wide, _) => [c, c].iter().collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct World {
+    pub warehouse: Warehouse,
+    robot: usize,
+    moves: LinkedList<Mov>,
+}
+
+pub fn parse_input(strs: &[&str], layout: Layout) -> World {
     let mut s = strs.split(|x| x.is_empty());
     let grid: Vec<Vec<char>> = s
         .next()
         .unwrap()
         .iter()
+        .map(|x| x.chars().map(|c| layout.expand(c)).collect::<String>())
         .map(|x| x.chars().collect())
         .collect();
     let moves: LinkedList<Mov> = s
         .next()
-        .unwrap()
+        .unwrap_or_default()
         .join("")
         .chars()
         .map(|x| x.into())
         .collect();
-    let (x, y) = find_robot(&grid);
-
-    World {
-        r: Pos { x, y },
-        grid,
-        moves,
-    }
-}
-
-#[derive(Debug)]
-struct Pos {
-    x: usize,
-    y: usize,
-}
-
-#[derive(Debug)]
-struct World {
-    r: Pos,
-    grid: Vec<Vec<char>>,
-    moves: LinkedList<Mov>,
-}
-
-#[derive(Debug)]
-struct WorldX2 {
-    r: Pos,
-    grid: Vec<Vec<char>>,
-    moves: LinkedList<Mov>,
-}
-
-fn find_robot(grid: &Vec<Vec<char>>) -> (usize, usize) {
-    grid.iter()
-        .enumerate()
-        .find_map(|(y, line)| {
-            line.iter()
-                .enumerate()
-                .find_map(|(x, c)| if *c == '@' { Some((x, y)) } else { None })
-        })
-        .expect("Cannot find robot")
-}
-
-fn x2_world(w: World) -> WorldX2 {
-    let w2_grid: Vec<Vec<char>> = w
-        .grid
-        .iter()
-        .map(|line| {
-            line.iter()
-                .flat_map(|c| match c {
-                    '@' => vec!['@', '.'],
-                    'O' => vec!['[', ']'],
-                    _ => vec![*c, *c],
-                })
-                .collect()
-        })
-        .collect();
-    let (x, y) = find_robot(&w2_grid);
-
-    WorldX2 {
-        r: Pos { x, y },
-        grid: w2_grid,
-        moves: w.moves,
-    }
-}
-
-trait HasGrid {
-    fn get_grid(&self) -> &Vec<Vec<char>>;
-}
-
-trait Drawable: HasGrid {
-    fn draw_grid(&self) {
-        for line in self.get_grid().iter() {
-            println!("{}", line.iter().collect::<String>());
-        }
-    }
-}
-
-trait Gps: HasGrid {
-    const GPS_CHAR: char;
-    fn get_gps(&self) -> usize {
-        self.get_grid()
-            .iter()
-            .enumerate()
-            .map(|(y, line)| {
-                line.iter()
-                    .enumerate()
-                    .map(|(x, c)| if *c == Self::GPS_CHAR { 100 * y + x } else { 0 })
-                    .sum::<usize>()
-            })
-            .sum()
-    }
-}
-
-trait CanStep: Drawable {
-    fn step(&mut self) -> Option<()>;
-}
-
-impl HasGrid for World {
-    fn get_grid(&self) -> &Vec<Vec<char>> {
-        &self.grid
-    }
-}
-
-impl HasGrid for &mut World {
-    fn get_grid(&self) -> &Vec<Vec<char>> {
-        &self.grid
-    }
-}
-
-impl Gps for World {
-    const GPS_CHAR: char = 'O';
-}
-
-impl Drawable for World {}
 
-impl CanStep for World {
-    fn step(&mut self) -> Option<()> {
-        let m = self.moves.pop_front()?;
-        //println!("{:?}", m);
-        let (shift_x, shift_y) = m.into();
-        let (mut x, mut y) = (self.r.x, self.r.y);
-        let mut stack: Vec<(usize, usize)> = vec![(self.r.x, self.r.y)];
-        loop {
-            (x, y) = (
-                x.checked_add_signed(shift_x).unwrap(),
-                y.checked_add_signed(shift_y).unwrap(),
-            );
-            let ch = self.grid[y][x];
-            match ch {
-                '#' => {
-                    stack.clear();
-                    break;
-                }
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let mut warehouse = Warehouse::new(width, grid.len());
+    let mut robot = None;
+    for (y, line) in grid.iter().enumerate() {
+        for (x, c) in line.iter().enumerate() {
+            match c {
+                '#' => warehouse.add_wall((x, y)),
+                '@' => robot = Some(warehouse.add((x, y), vec![((0, 0), '@')])),
                 'O' => {
-                    stack.push((x, y));
+                    warehouse.add((x, y), vec![((0, 0), 'O')]);
                 }
-                _ => {
-                    break;
+                '[' => {
+                    assert_eq!(line.get(x + 1), Some(&']'), "Unclosed box at {}, {}", x, y);
+                    warehouse.add((x, y), vec![((0, 0), '['), ((1, 0), ']')]);
                 }
+                ']' => assert_eq!(
+                    x.checked_sub(1).and_then(|i| line.get(i)),
+                    Some(&'['),
+                    "Unopened box at {}, {}",
+                    x,
+                    y
+                ),
+                _ => {}
             }
         }
-        //dbg!(&stack);
-        while let Some((x, y)) = stack.pop() {
-            let (new_x, new_y) = (
-                x.checked_add_signed(shift_x).unwrap(),
-                y.checked_add_signed(shift_y).unwrap(),
-            );
-            if self.grid[y][x] == '@' {
-                self.r.x = new_x;
-                self.r.y = new_y;
-            }
-            self.grid[new_y][new_x] = self.grid[y][x];
-            self.grid[y][x] = '.';
-        }
-
-        //self.draw_grid();
-
-        Some(())
     }
-}
 
-impl HasGrid for WorldX2 {
-    fn get_grid(&self) -> &Vec<Vec<char>> {
-        &self.grid
+    World {
+        warehouse,
+        robot: robot.expect("Cannot find robot"),
+        moves,
     }
 }
 
-impl Drawable for WorldX2 {}
-
-impl Gps for WorldX2 {
-    const GPS_CHAR: char = '[';
-}
-
-impl WorldX2 {
-    fn step_hor(&mut self) -> Option<()> {
-        let m = self.moves.pop_front()?;
-        //println!("{:?}", m);
-        let (shift_x, shift_y) = m.into();
-        let (mut x, mut y) = (self.r.x, self.r.y);
-        let mut stack: Vec<(usize, usize)> = vec![(self.r.x, self.r.y)];
-        loop {
-            (x, y) = (
-                x.checked_add_signed(shift_x).unwrap(),
-                y.checked_add_signed(shift_y).unwrap(),
-            );
-            let ch = self.grid[y][x];
-            match ch {
-                '#' => {
-                    stack.clear();
-                    break;
-                }
-                '[' | ']' => {
-                    stack.push((x, y));
-                }
-                _ => {
-                    break;
-                }
-            }
-        }
-        //dbg!(&stack);
-        while let Some((x, y)) = stack.pop() {
-            let (new_x, new_y) = (
-                x.checked_add_signed(shift_x).unwrap(),
-                y.checked_add_signed(shift_y).unwrap(),
-            );
-            if self.grid[y][x] == '@' {
-                self.r.x = new_x;
-                self.r.y = new_y;
-            }
-            self.grid[new_y][new_x] = self.grid[y][x];
-            self.grid[y][x] = '.';
-        }
-
-        //self.draw_grid();
-
+impl World {
+    /// Makes the robot's next move. Returns `None` once all moves are made.
+    pub fn step(&mut self) -> Option<()> {
+        let d: Dir = self.moves.pop_front()?.into();
+        self.warehouse.push(self.robot, d);
         Some(())
     }
 
-    fn step_vertical(&mut self) -> Option<()> {
-        let m = self.moves.pop_front()?;
-        let (_, shift_y) = m.into();
-        let (x, mut y) = (self.r.x, self.r.y);
-        let mut stack: Vec<Vec<(usize, usize)>> = vec![];
-        stack.push(vec![(self.r.x, self.r.y)]);
-
-        let new_y = y.checked_add_signed(shift_y).unwrap();
-        let mut check_locs: Vec<Vec<(usize, usize)>> = vec![vec![(x, new_y)]];
-
-        if self.grid[new_y][x] == '.' {
-            self.grid[y][x] = '.';
-            self.r.y = new_y;
-            self.grid[new_y][x] = '@';
-            return Some(());
-        }
-
-        'outer: while let Some(step_locs) = check_locs.pop() {
-            let mut all_locs_free = true;
-            let mut next_step_locs: HashSet<(usize, usize)> = HashSet::new();
-            let mut locs_to_check: HashSet<(usize, usize)> = HashSet::from_iter(step_locs);
-
-            let mut next_slice = HashSet::new();
-            y = y.checked_add_signed(shift_y).unwrap();
-            let mut d: Vec<char> = vec![];
-            for l in locs_to_check.iter() {
-                d.push(self.grid[l.1][l.0]);
-            }
-            while let Some(loc) = locs_to_check.iter().next().cloned() {
-                locs_to_check.remove(&loc);
-                let ch = self.grid[loc.1][loc.0];
-                match ch {
-                    '#' => {
-                        stack.clear();
-                        check_locs.clear();
-                        break 'outer;
-                    }
-                    '[' => {
-                        let next_y = loc.1.checked_add_signed(shift_y).unwrap();
-                        next_step_locs.insert((loc.0, next_y));
-                        next_slice.insert(loc);
-                        let close = (loc.0 + 1, loc.1);
-                        if !next_slice.contains(&close) {
-                            locs_to_check.insert(close);
-                        }
-                        all_locs_free = false;
-                    }
-                    ']' => {
-                        let next_y = loc.1.checked_add_signed(shift_y).unwrap();
-                        next_step_locs.insert((loc.0, next_y));
-                        next_slice.insert(loc);
-                        let close = (loc.0 - 1, loc.1);
-                        if !next_slice.contains(&close) {
-                            locs_to_check.insert(close);
-                        }
-                        all_locs_free = false;
-                    }
-                    _ => {}
-                }
-            }
-            if all_locs_free {
-                break;
-            }
-            stack.push(next_slice.into_iter().collect());
-            check_locs.push(next_step_locs.into_iter().collect());
-        }
-        if stack.len() > 1 {
-            self.r.y = self.r.y.checked_add_signed(shift_y).unwrap();
-        }
-        while let Some(objs) = stack.pop() {
-            for obj in objs {
-                let new_y = obj.1.checked_add_signed(shift_y).unwrap();
-                self.grid[new_y][obj.0] = self.grid[obj.1][obj.0];
-                self.grid[obj.1][obj.0] = '.';
-            }
-        }
-        Some(())
+    pub fn run(&mut self) {
+        while self.step().is_some() {}
     }
-}
 
-impl CanStep for WorldX2 {
-    fn step(&mut self) -> Option<()> {
-        match self.moves.front() {
-            Some(Mov::Right | Mov::Left) => self.step_hor(),
-            Some(Mov::Up | Mov::Down) => self.step_vertical(),
-            None => None,
-        }
+    /// Sum of `100 * y + x` over the top-left corners of the boxes.
+    pub fn gps(&self) -> usize {
+        self.warehouse
+            .objects()
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| *id != self.robot)
+            .map(|(_, o)| 100 * o.pos.1 + o.pos.0)
+            .sum()
     }
 }
 
 pub fn solve_part1(strs: &[&str]) -> usize {
-    let mut w = parse_input(strs);
-    while w.step().is_some() {}
-    w.get_gps()
+    let mut w = parse_input(strs, Layout::Single);
+    w.run();
+    w.gps()
 }
 
 pub fn solve_part2(strs: &[&str]) -> usize {
-    let mut w = x2_world(parse_input(strs));
-    let n = w.moves.len();
-    for _ in 0..n {
-        w.step();
-    }
-    w.get_gps()
+    let mut w = parse_input(strs, Layout::Wide);
+    w.run();
+    w.gps()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.strip_prefix("\n")
-            .unwrap()
-            .lines()
-            .map(str::trim)
-            .collect()
-    }
-
-    const EXAMPLE: &str = r#"
-    ########
-    #..O.O.#
-    ##@.O..#
-    #...O..#
-    #.#.O..#
-    #...O..#
-    #......#
-    ########
-    
-    <^^>>>vv<v>>v<<"#;
-
-    const EXAMPLE_BIG: &str = r#"
-##########
-#..O..O.O#
-#......O.#
-#.OO..O.O#
-#..O@..O.#
-#O#..O...#
-#O..O..O.#
-#.OO.O.OO#
-#....O...#
-##########
-
-<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
-vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
-><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
-<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
-^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
-^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
->^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
-<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
-^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
-v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"#;
-
-    const EXAMPLE_PART2: &str = r#"
-########
-#......#
-#......#
-#[]..[]#
-#.[][].#
-#..[]..#
-#...@..#
-########
-
-^^^^^
-"#;
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_example() {
-        let input = read_input(EXAMPLE);
-        let mut w = parse_input(&input);
-        w.draw_grid();
-        while w.step().is_some() {}
-        assert_eq!(w.get_gps(), 2028);
+        let f = fixture!("small");
+        let mut w = parse_input(&f.lines(), Layout::Single);
+        w.run();
+        f.assert_part1(w.gps());
     }
 
     #[test]
     fn test_example_big() {
-        let input = read_input(EXAMPLE_BIG);
-        let mut w = parse_input(&input);
-        w.draw_grid();
-        while w.step().is_some() {}
-        assert_eq!(w.get_gps(), 10092);
+        let f = fixture!("big");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_example_x2_small() {
-        let input = read_input(EXAMPLE_PART2);
-        let w = parse_input(&input);
-        let mut w = WorldX2 {
-            r: w.r,
-            grid: w.grid,
-            moves: w.moves,
-        };
-
-        while w.step().is_some() {
-            w.draw_grid();
-        }
-
-        assert_eq!(w.get_gps(), 2028);
+        let f = fixture!("wide");
+        let mut w = parse_input(&f.lines(), Layout::Single);
+        w.run();
+        f.assert_part2(w.gps());
+        assert_eq!(
+            w.warehouse.render()[1..4],
+            ["#[]..[]#", "#.[][].#", "#..[]..#"]
+        );
     }
 
     #[test]
     fn test_example_x2_big() {
-        let input = read_input(EXAMPLE_BIG);
-        let mut w = x2_world(parse_input(&input));
-        w.draw_grid();
-        while w.step().is_some() {
-            w.draw_grid();
-        }
+        let f = fixture!("big");
+        f.assert_part2(solve_part2(&f.lines()));
+    }
 
-        assert_eq!(w.get_gps(), 2028);
+    #[test]
+    fn test_wide_layout() {
+        let w = parse_input(&["#####", "#.O@#", "#####"], Layout::Wide);
+        assert_eq!(w.warehouse.render()[1], "##..[]@.##");
+        assert_eq!(w.gps(), 104);
     }
+
+    #[test]
+    #[should_panic(expected = "Unopened box at 0, 1")]
+    fn test_unopened_box_at_edge() {
+        parse_input(&["#####", "]..@#", "#####"], Layout::Single);
+    }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,63 @@
+//! Reindeer mazes: a random spanning tree of corridors with a few walls
+//! knocked through, so that several routes can tie for best.
+
+use aoc_runner::rng::Rng;
+
+/// Carves a depth-first spanning tree over the odd cells of `grid`.
+fn carve(grid: &mut [Vec<char>], from: (usize, usize), r: &mut Rng) {
+    let (w, h) = (grid[0].len(), grid.len());
+    grid[from.1][from.0] = '.';
+    let mut stack = vec![from];
+    while let Some(&(x, y)) = stack.last() {
+        let mut next: Vec<(usize, usize)> = [(0, -2), (2, 0), (0, 2), (-2, 0)]
+            .iter()
+            .filter_map(|(dx, dy)| Some((x.checked_add_signed(*dx)?, y.checked_add_signed(*dy)?)))
+            .filter(|(nx, ny)| *nx < w - 1 && *ny < h - 1 && grid[*ny][*nx] == '#')
+            .collect();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        r.shuffle(&mut next);
+        let (nx, ny) = next[0];
+        grid[(y + ny) / 2][(x + nx) / 2] = '.';
+        grid[ny][nx] = '.';
+        stack.push((nx, ny));
+    }
+}
+
+/// A `size` by `size` maze (rounded up to odd) from `S` in the bottom-left
+/// corner to `E` in the top-right, with one wall in fifty between corridors
+/// removed.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let n = size.max(5) | 1;
+    let mut grid = vec![vec!['#'; n]; n];
+    carve(&mut grid, (1, n - 2), r);
+    for _ in 0..n * n / 50 {
+        let (x, y) = (r.range(1, n - 2), r.range(1, n - 2));
+        if (x + y) % 2 == 1 {
+            grid[y][x] = '.';
+        }
+    }
+    grid[n - 2][1] = 'S';
+    grid[1][n - 2] = 'E';
+    grid.into_iter()
+        .map(|l| l.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_maze() {
+        let input = generate(41, &mut Rng::new(16));
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 41);
+        assert!(solve_part1(&lines) > 1000);
+        assert!(solve_part2(&lines) > 40);
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashSet},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Costs {
+    pub step: usize,
+    pub turn: usize,
+}
+
+impl Default for Costs {
+    fn default() -> Self {
+        Costs {
+            step: 1,
+            turn: 1000,
+        }
+    }
+}
+
+/// Clockwise from East, the direction every reindeer starts in.
+const DIRS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+/// Position and facing, packed as `(y * width + x) * 4 + dir`.
+type State = usize;
+
+pub struct Maze {
+    grid: Vec<Vec<u8>>,
+    width: usize,
+    starts: Vec<(usize, usize)>,
+    ends: Vec<(usize, usize)>,
+}
+
+impl Maze {
+    pub fn parse(strs: &[&str]) -> Maze {
+        let grid: Vec<Vec<u8>> = strs.iter().map(|s| s.bytes().collect()).collect();
+        let width = grid.first().map_or(0, |l| l.len());
+        let find = |c: u8| -> Vec<(usize, usize)> {
+            grid.iter()
+                .enumerate()
+                .flat_map(|(y, l)| l.iter().enumerate().map(move |(x, ch)| (x, y, *ch)))
+                .filter_map(|(x, y, ch)| (ch == c).then_some((x, y)))
+                .collect()
+        };
+        let starts = find(b'S');
+        let ends = find(b'E');
+        Maze {
+            grid,
+            width,
+            starts,
+            ends,
+        }
+    }
+
+    fn state(&self, (x, y): (usize, usize), dir: usize) -> State {
+        (y * self.width + x) * 4 + dir
+    }
+
+    fn tile(&self, s: State) -> (usize, usize) {
+        let i = s / 4;
+        (i % self.width, i / self.width)
+    }
+
+    fn is_open(&self, x: usize, y: usize) -> bool {
+        self.grid
+            .get(y)
+            .and_then(|l| l.get(x))
+            .is_some_and(|ch| *ch != b'#')
+    }
+
+    fn moves(&self, s: State, costs: Costs) -> impl Iterator<Item = (State, usize)> + '_ {
+        let (x, y) = self.tile(s);
+        let dir = s % 4;
+        let (dx, dy) = DIRS[dir];
+        let forward = x
+            .checked_add_signed(dx)
+            .zip(y.checked_add_signed(dy))
+            .filter(|(nx, ny)| self.is_open(*nx, *ny))
+            .map(|p| (self.state(p, dir), costs.step));
+        let turns = [(dir + 1) % 4, (dir + 3) % 4].map(|d| (self.state((x, y), d), costs.turn));
+        forward.into_iter().chain(turns)
+    }
+
+    /// Dijkstra from every start tile (facing East) to the nearest end tile,
+    /// keeping all predecessors that lie on a cheapest route.
+    pub fn best_paths(&self, costs: Costs) -> Option<BestPaths> {
+        assert!(costs.step > 0 && costs.turn > 0, "costs must be positive");
+        let n = self.grid.len() * self.width * 4;
+        let mut dist = vec![usize::MAX; n];
+        let mut parents: Vec<Vec<State>> = vec![vec![]; n];
+        let mut heap = BinaryHeap::new();
+        for p in self.starts.iter() {
+            let s = self.state(*p, 0);
+            dist[s] = 0;
+            heap.push(Reverse((0, s)));
+        }
+
+        while let Some(Reverse((cost, s))) = heap.pop() {
+            if cost > dist[s] {
+                continue;
+            }
+            for (next, c) in self.moves(s, costs) {
+                let next_cost = cost + c;
+                if next_cost < dist[next] {
+                    dist[next] = next_cost;
+                    parents[next] = vec![s];
+                    heap.push(Reverse((next_cost, next)));
+                } else if next_cost == dist[next] {
+                    parents[next].push(s);
+                }
+            }
+        }
+
+        let end_states: Vec<State> = self
+            .ends
+            .iter()
+            .flat_map(|p| (0..4).map(|d| self.state(*p, d)))
+            .collect();
+        let cost = end_states.iter().map(|s| dist[*s]).min()?;
+        if cost == usize::MAX {
+            return None;
+        }
+        Some(BestPaths {
+            cost,
+            ends: end_states
+                .into_iter()
+                .filter(|s| dist[*s] == cost)
+                .collect(),
+            parents,
+            width: self.width,
+        })
+    }
+
+    /// The maze with every tile of `tiles` replaced by `O`.
+    pub fn render(&self, tiles: &HashSet<(usize, usize)>) -> Vec<String> {
+        self.grid
+            .iter()
+            .enumerate()
+            .map(|(y, l)| {
+                l.iter()
+                    .enumerate()
+                    .map(|(x, ch)| {
+                        if tiles.contains(&(x, y)) {
+                            'O'
+                        } else {
+                            *ch as char
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+pub struct BestPaths {
+    pub cost: usize,
+    ends: Vec<State>,
+    parents: Vec<Vec<State>>,
+    width: usize,
+}
+
+impl BestPaths {
+    fn tile(&self, s: State) -> (usize, usize) {
+        let i = s / 4;
+        (i % self.width, i / self.width)
+    }
+
+    /// Tiles that are part of at least one cheapest route.
+    pub fn tiles(&self) -> HashSet<(usize, usize)> {
+        let mut seen: HashSet<State> = self.ends.iter().copied().collect();
+        let mut stack = self.ends.clone();
+        while let Some(s) = stack.pop() {
+            for p in self.parents[s].iter() {
+                if seen.insert(*p) {
+                    stack.push(*p);
+                }
+            }
+        }
+        seen.into_iter().map(|s| self.tile(s)).collect()
+    }
+
+    /// Up to `cap` distinct cheapest routes, each as the tiles visited from
+    /// start to end. Routes differing only in where the reindeer turns visit
+    /// the same tiles and are listed once.
+    pub fn routes(&self, cap: usize) -> Vec<Vec<(usize, usize)>> {
+        let mut res = vec![];
+        let mut seen = HashSet::new();
+        let mut stack: Vec<Vec<State>> = self.ends.iter().map(|s| vec![*s]).collect();
+        while let Some(route) = stack.pop() {
+            if res.len() == cap {
+                break;
+            }
+            let last = *route.last().unwrap();
+            if self.parents[last].is_empty() {
+                let mut tiles: Vec<(usize, usize)> =
+                    route.iter().rev().map(|s| self.tile(*s)).collect();
+                // Turning in place repeats a tile.
+                tiles.dedup();
+                if seen.insert(tiles.clone()) {
+                    res.push(tiles);
+                }
+                continue;
+            }
+            for p in self.parents[last].iter() {
+                let mut next = route.clone();
+                next.push(*p);
+                stack.push(next);
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_fixture::fixture;
+
+    #[test]
+    fn test_render() {
+        let f = fixture!("example2");
+        let maze = Maze::parse(&f.lines());
+        let best = maze.best_paths(Costs::default()).unwrap();
+        f.assert_part1(best.cost);
+        assert_eq!(
+            maze.render(&best.tiles()),
+            fixture!("example2_tiles").lines()
+        );
+    }
+
+    #[test]
+    fn test_routes() {
+        let maze = Maze::parse(&fixture!("example2").lines());
+        let best = maze.best_paths(Costs::default()).unwrap();
+        let routes = best.routes(10);
+        assert_eq!(routes.len(), 2);
+        for r in routes.iter() {
+            assert_eq!(r.first(), Some(&(1, 15)));
+            assert_eq!(r.last(), Some(&(15, 1)));
+        }
+        assert_eq!(best.routes(1).len(), 1);
+
+        // Turning around clockwise or anticlockwise visits the same tiles.
+        let maze = Maze::parse(&["#####", "#E.S#", "#####"]);
+        let best = maze.best_paths(Costs::default()).unwrap();
+        assert_eq!(best.routes(10), vec![vec![(3, 1), (2, 1), (1, 1)]]);
+    }
+
+    #[test]
+    fn test_costs() {
+        let maze = Maze::parse(&["#####", "#S.E#", "#####"]);
+        let costs = Costs { step: 5, turn: 7 };
+        assert_eq!(maze.best_paths(costs).unwrap().cost, 10);
+
+        let maze = Maze::parse(&["#####", "#E.S#", "#####"]);
+        assert_eq!(maze.best_paths(costs).unwrap().cost, 24);
+
+        let maze = Maze::parse(&["#######", "#S.#.E#", "#######"]);
+        assert!(maze.best_paths(costs).is_none());
+
+        let maze = Maze::parse(&["#######", "#S.E.S#", "#######"]);
+        assert_eq!(maze.best_paths(costs).unwrap().cost, 10);
+    }
+}
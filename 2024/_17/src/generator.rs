@@ -0,0 +1,43 @@
+//! Programs for the three-bit computer in the shape of the puzzle's: a loop
+//! that mixes the low bits of A, outputs three bits and shifts A by three.
+
+use aoc_runner::rng::Rng;
+
+/// A program printing `size` values (at most 20) before it halts.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let digits = size.clamp(1, 20);
+    let a = (1..digits).fold(r.range(1, 7), |a, _| a * 8 + r.below(8));
+
+    let mut mix = [[4, r.below(8)], [1, r.below(8)]];
+    r.shuffle(&mut mix);
+    let mut tail = [[0, 3], [5, 5]];
+    r.shuffle(&mut tail);
+    let program: Vec<String> = [[2, 4], [1, r.below(8)], [7, 5]]
+        .iter()
+        .chain(mix.iter())
+        .chain(tail.iter())
+        .chain([[3, 0]].iter())
+        .flatten()
+        .map(|n| n.to_string())
+        .collect();
+    format!(
+        "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}",
+        a,
+        program.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_part1;
+
+    #[test]
+    fn test_generated_program() {
+        for size in [1, 10, 20] {
+            let input = generate(size, &mut Rng::new(17));
+            let out = solve_part1(&input.lines().collect::<Vec<_>>());
+            assert_eq!(out.split(',').count(), size);
+        }
+    }
+}
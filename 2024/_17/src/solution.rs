@@ -239,29 +239,17 @@ pub fn solve_part2(strs: &[&str]) -> isize {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const EXAMPLE: &str = "
-    Register A: 729
-    Register B: 0
-    Register C: 0
-
-    Program: 0,1,5,4,3,0";
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.strip_prefix("\n")
-            .unwrap()
-            .lines()
-            .map(str::trim)
-            .collect()
-    }
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_name() {
-        let input = read_input(EXAMPLE);
-        let mut r = parse_runtime(&input).unwrap();
+        let f = fixture!("example");
+        let mut r = parse_runtime(&f.lines()).unwrap();
         r.run();
         let out: Vec<String> = r.out.iter().map(|x| x.to_string()).collect();
-        let s = out.join(",");
-        dbg!(s);
+        f.assert_part1(out.join(","));
+        f.assert_part1(solve_part1(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,89 @@
+//! Falling bytes on a square memory space.
+
+use std::collections::VecDeque;
+
+use aoc_runner::rng::Rng;
+
+fn reachable(n: usize, bytes: &[(usize, usize)]) -> bool {
+    let mut blocked = vec![false; n * n];
+    for (x, y) in bytes {
+        blocked[y * n + x] = true;
+    }
+    let mut queue = VecDeque::from([(0, 0)]);
+    blocked[0] = true;
+    while let Some((x, y)) = queue.pop_front() {
+        if (x, y) == (n - 1, n - 1) {
+            return true;
+        }
+        for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+            let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+                continue;
+            };
+            if nx < n && ny < n && !blocked[ny * n + nx] {
+                blocked[ny * n + nx] = true;
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+    false
+}
+
+/// Bytes part 1 can let fall on a generated space of side `n`: the puzzle's
+/// share of 1024 out of 71 by 71 cells.
+pub fn open_bytes(n: usize) -> usize {
+    n * n / 5
+}
+
+/// Every cell of a `size` by `size` space (at least 2) but the corners, in
+/// falling order. The first `open_bytes(size)` always leave the exit open;
+/// solve with `--size <size> --bytes <open_bytes(size)>`.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let n = size.max(2);
+    let mut cells: Vec<(usize, usize)> = (0..n * n)
+        .map(|i| (i % n, i / n))
+        .filter(|p| *p != (0, 0) && *p != (n - 1, n - 1))
+        .collect();
+    loop {
+        r.shuffle(&mut cells);
+        // Bytes that fall before the exit is cut off.
+        let (mut lo, mut hi) = (0, cells.len());
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if reachable(n, &cells[..mid]) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        if lo >= open_bytes(n) {
+            return cells
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2, Params};
+
+    #[test]
+    fn test_generated_bytes() {
+        for size in [0, 7, 71] {
+            let input = generate(size, &mut Rng::new(18));
+            let lines: Vec<&str> = input.lines().collect();
+            let n = size.max(2);
+            let params = Params {
+                size: Some(n),
+                bytes: Some(open_bytes(n)),
+            };
+            assert!(solve_part1(&lines, params).unwrap() >= 2 * (n - 1));
+            let (x, y) = solve_part2(&lines, params).unwrap();
+            let blocking = lines.iter().position(|l| *l == format!("{},{}", x, y));
+            assert!(blocking.unwrap() >= open_bytes(n));
+        }
+    }
+}
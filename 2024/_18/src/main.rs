@@ -1,28 +1,70 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::{env, io};
 
+use aoc_input::Input;
+use aoc_runner::Day;
+
+mod generator;
+mod memory;
 mod solution;
 
-use solution::{solve_part1, solve_part2};
+use memory::Search;
+use solution::{first_blocking, parse, solve_part1, solve_part2, Params};
 
-fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v: Vec<&str> = v.iter().map(String::as_str).collect();
-    println!("Part1:");
-    let res = solve_part1(&v);
-    println!("{}", res);
+/// `--size N` and `--bytes N` from the command line.
+fn args_params() -> io::Result<Params> {
+    let args: Vec<String> = env::args().collect();
+    let value = |name: &str, min: usize| match args.iter().position(|a| a == name) {
+        None => Ok(None),
+        Some(i) => args
+            .get(i + 1)
+            .and_then(|n| n.parse().ok())
+            .filter(|n| *n >= min)
+            .map(Some)
+            .ok_or_else(|| {
+                let msg = format!("{} needs a number of at least {}", name, min);
+                io::Error::new(io::ErrorKind::InvalidInput, msg)
+            }),
+    };
+    Ok(Params {
+        size: value("--size", 1)?,
+        bytes: value("--bytes", 0)?,
+    })
+}
 
-    let res = solve_part2(&v);
+/// The `size` and `bytes` given with the input, in the example's header or
+/// the answers file.
+fn input_params(i: &Input) -> Params {
+    let value = |key: &str| i.param(key).and_then(|v| v.parse().ok());
+    Params {
+        size: value("size"),
+        bytes: value("bytes"),
+    }
+}
 
-    println!("Part2:");
-    println!("{:?}", res);
+fn main() -> io::Result<()> {
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    // The command line wins over the input.
+    let params = args_params()?;
+    day.try_part(1, move |i| {
+        solve_part1(&i.lines(), params.or(input_params(i)))
+    });
+    day.try_part(2, move |i| {
+        solve_part2(&i.lines(), params.or(input_params(i))).map(|(x, y)| format!("{},{}", x, y))
+    });
+    day.extras(move |i| {
+        let v = i.lines();
+        let params = params.or(input_params(i));
+        if let Ok(b) = first_blocking(&v, params, Search::Bisect) {
+            println!("Last open path before byte {}:", b.index);
+            let space = parse(&v, params.size.unwrap()).unwrap();
+            for line in space.render(b.index, &b.last_path) {
+                println!("{}", line);
+            }
+        }
+    });
 
-    Ok(())
+    day.finish()
 }
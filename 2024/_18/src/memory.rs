@@ -0,0 +1,261 @@
+use std::{collections::VecDeque, fmt};
+
+type Pos = (usize, usize);
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line in the input, which is also the byte's 1-based index.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The byte whose fall first cuts the exit off, and the shortest path that
+/// was still open just before it landed.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blocking {
+    pub index: usize,
+    pub byte: Pos,
+    pub last_path: Vec<Pos>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Search {
+    Bisect,
+    UnionFind,
+}
+
+pub struct MemorySpace {
+    width: usize,
+    height: usize,
+    bytes: Vec<Pos>,
+}
+
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parent[a] = b;
+    }
+}
+
+impl MemorySpace {
+    /// Fails on the first byte that falls outside the space.
+    pub fn new(width: usize, height: usize, bytes: Vec<Pos>) -> Result<MemorySpace, ParseError> {
+        if let Some(i) = bytes.iter().position(|(x, y)| *x >= width || *y >= height) {
+            let (x, y) = bytes[i];
+            return Err(ParseError {
+                line: i + 1,
+                message: format!("Byte {},{} is outside the {}x{} space", x, y, width, height),
+            });
+        }
+        Ok(MemorySpace {
+            width,
+            height,
+            bytes,
+        })
+    }
+
+    /// One `x,y` byte per line.
+    pub fn parse_bytes(strs: &[&str]) -> Result<Vec<Pos>, ParseError> {
+        strs.iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let err = |message: String| ParseError {
+                    line: i + 1,
+                    message,
+                };
+                let (x, y) = s
+                    .split_once(',')
+                    .ok_or_else(|| err(format!("Byte {:?} is not x,y", s)))?;
+                let coord = |c: &str| {
+                    c.trim()
+                        .parse()
+                        .map_err(|_| err(format!("Cannot parse coordinate {:?}", c)))
+                };
+                Ok((coord(x)?, coord(y)?))
+            })
+            .collect()
+    }
+
+    fn idx(&self, (x, y): Pos) -> usize {
+        y * self.width + x
+    }
+
+    fn goal(&self) -> Pos {
+        (self.width - 1, self.height - 1)
+    }
+
+    /// Number of fallen bytes on each cell after the first `n` bytes.
+    fn corrupted(&self, n: usize) -> Vec<usize> {
+        let mut grid = vec![0; self.width * self.height];
+        for b in self.bytes.iter().take(n) {
+            grid[self.idx(*b)] += 1;
+        }
+        grid
+    }
+
+    fn neighbours(&self, (x, y): Pos) -> impl Iterator<Item = Pos> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| {
+                Some((x.checked_add_signed(dx)?, y.checked_add_signed(dy)?))
+            })
+            .filter(|(x, y)| *x < self.width && *y < self.height)
+    }
+
+    /// Shortest path from the top-left to the bottom-right corner once the
+    /// first `n` bytes have fallen, both corners included.
+    pub fn shortest_path(&self, n: usize) -> Option<Vec<Pos>> {
+        let grid = self.corrupted(n);
+        let start = (0, 0);
+        if grid[self.idx(start)] > 0 {
+            return None;
+        }
+        let mut prev: Vec<Option<Pos>> = vec![None; grid.len()];
+        let mut seen = vec![false; grid.len()];
+        seen[self.idx(start)] = true;
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(p) = queue.pop_front() {
+            if p == self.goal() {
+                let mut path = vec![p];
+                while let Some(q) = prev[self.idx(*path.last().unwrap())] {
+                    path.push(q);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in self.neighbours(p) {
+                let i = self.idx(next);
+                if grid[i] == 0 && !seen[i] {
+                    seen[i] = true;
+                    prev[i] = Some(p);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    fn blocking(&self, index: usize) -> Blocking {
+        Blocking {
+            index,
+            byte: self.bytes[index],
+            last_path: self.shortest_path(index).unwrap_or_default(),
+        }
+    }
+
+    pub fn first_blocking(&self, search: Search) -> Option<Blocking> {
+        match search {
+            Search::Bisect => self.first_blocking_bisect(),
+            Search::UnionFind => self.first_blocking_union_find(),
+        }
+    }
+
+    /// Bisects over the number of fallen bytes, running one BFS per probe.
+    pub fn first_blocking_bisect(&self) -> Option<Blocking> {
+        if self.shortest_path(self.bytes.len()).is_some() {
+            return None;
+        }
+        // Open with `lo` bytes, closed with `hi`.
+        let (mut lo, mut hi) = (0, self.bytes.len());
+        while hi - lo > 1 {
+            let mid = (lo + hi) / 2;
+            if self.shortest_path(mid).is_some() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Some(self.blocking(hi - 1))
+    }
+
+    /// Lets every byte fall, then lifts them again from the last one until
+    /// both corners end up in the same connected region of free cells.
+    pub fn first_blocking_union_find(&self) -> Option<Blocking> {
+        let mut grid = self.corrupted(self.bytes.len());
+        let mut uf = UnionFind::new(grid.len());
+        let connect = |uf: &mut UnionFind, grid: &[usize], p: Pos| {
+            for q in self.neighbours(p) {
+                if grid[self.idx(q)] == 0 {
+                    uf.union(self.idx(p), self.idx(q));
+                }
+            }
+        };
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if grid[self.idx((x, y))] == 0 {
+                    connect(&mut uf, &grid, (x, y));
+                }
+            }
+        }
+        let (start, goal) = (self.idx((0, 0)), self.idx(self.goal()));
+        let is_open = |uf: &mut UnionFind, grid: &[usize]| {
+            grid[start] == 0 && uf.find(start) == uf.find(goal)
+        };
+        if is_open(&mut uf, &grid) {
+            return None;
+        }
+
+        for (i, b) in self.bytes.iter().enumerate().rev() {
+            grid[self.idx(*b)] -= 1;
+            if grid[self.idx(*b)] == 0 {
+                connect(&mut uf, &grid, *b);
+            }
+            if is_open(&mut uf, &grid) {
+                return Some(self.blocking(i));
+            }
+        }
+        None
+    }
+
+    /// The memory space after `n` bytes, with `path` marked `O`.
+    pub fn render(&self, n: usize, path: &[Pos]) -> Vec<String> {
+        let grid = self.corrupted(n);
+        let mut lines: Vec<Vec<u8>> = (0..self.height)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| {
+                        if grid[self.idx((x, y))] > 0 {
+                            b'#'
+                        } else {
+                            b'.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        for (x, y) in path {
+            lines[*y][*x] = b'O';
+        }
+        lines
+            .into_iter()
+            .map(|l| String::from_utf8(l).unwrap())
+            .collect()
+    }
+}
@@ -1,230 +1,208 @@
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-    usize,
-};
-
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-struct Move {
-    cost: usize,
-    p: (usize, usize),
+use std::fmt;
+
+use crate::memory::{Blocking, MemorySpace, ParseError, Search};
+
+/// The side of the memory space and the number of bytes part 1 lets fall.
+/// Neither can be told from the bytes, so both must be given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Params {
+    pub size: Option<usize>,
+    pub bytes: Option<usize>,
 }
 
-impl Ord for Move {
-    fn cmp(&self, other: &Move) -> Ordering {
-        // Notice that the we flip the ordering here
-        other.cost.cmp(&self.cost)
+impl Params {
+    /// `self`, with what it leaves open taken from `other`.
+    pub fn or(self, other: Params) -> Params {
+        Params {
+            size: self.size.or(other.size),
+            bytes: self.bytes.or(other.bytes),
+        }
     }
-}
 
-impl PartialOrd for Move {
-    fn partial_cmp(&self, other: &Move) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn size(&self) -> Result<usize, SolveError> {
+        self.size.ok_or(SolveError::Missing("size"))
     }
-}
 
-impl Move {
-    fn new(to: (usize, usize)) -> Move {
-        Move { cost: 1, p: to }
+    fn bytes(&self) -> Result<usize, SolveError> {
+        self.bytes.ok_or(SolveError::Missing("bytes"))
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+    Parse(ParseError),
+    /// A parameter given neither on the command line nor with the input.
+    Missing(&'static str),
+    /// The exit is cut off once that many bytes have fallen.
+    Unreachable(usize),
+    NeverBlocked,
 }
 
-impl Dir {
-    fn get_shift(&self) -> (isize, isize) {
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Dir::Up => (0, -1),
-            Dir::Left => (-1, 0),
-            Dir::Right => (1, 0),
-            Dir::Down => (0, 1),
+            SolveError::Parse(e) => write!(f, "{}", e),
+            SolveError::Missing(key) => {
+                write!(f, "No {}: pass --{} or give it with the input", key, key)
+            }
+            SolveError::Unreachable(n) => write!(f, "Exit is unreachable after {} bytes", n),
+            SolveError::NeverBlocked => write!(f, "Exit is never blocked"),
         }
     }
 }
 
-#[derive(Debug)]
-struct World {
-    grid: Vec<Vec<char>>,
-    x_range: std::ops::Range<usize>,
-    y_range: std::ops::Range<usize>,
-    free_loc_char: char,
-}
+impl std::error::Error for SolveError {}
 
-impl World {
-    fn new(x_width: usize, y_width: usize, free_loc_char: char) -> World {
-        let x_range = 0..x_width;
-        let y_range = 0..y_width;
-        let grid = vec![vec![free_loc_char; x_width]; y_width];
-        World {
-            grid,
-            x_range,
-            y_range,
-            free_loc_char,
-        }
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> SolveError {
+        SolveError::Parse(e)
     }
+}
 
-    fn from_grid(strs: &[&str], free_loc_char: char) -> World {
-        let max_y = strs.len();
-        let max_x = strs[0].len();
-        let grid = strs.iter().map(|s| s.chars().collect()).collect();
-        let x_range = 0..max_x;
-        let y_range = 0..max_y;
-        World {
-            grid,
-            x_range,
-            y_range,
-            free_loc_char,
-        }
-    }
+/// The square memory space of side `size`.
+pub fn parse(strs: &[&str], size: usize) -> Result<MemorySpace, ParseError> {
+    MemorySpace::new(size, size, MemorySpace::parse_bytes(strs)?)
+}
 
-    fn is_in_bounds(&self, x: usize, y: usize) -> bool {
-        self.x_range.contains(&x) && self.y_range.contains(&y)
-    }
+pub fn first_blocking(
+    strs: &[&str],
+    params: Params,
+    search: Search,
+) -> Result<Blocking, SolveError> {
+    parse(strs, params.size()?)?
+        .first_blocking(search)
+        .ok_or(SolveError::NeverBlocked)
+}
 
-    fn get_char(&self, p: (usize, usize)) -> char {
-        self.grid[p.1][p.0]
-    }
+pub fn solve_part1(strs: &[&str], params: Params) -> Result<usize, SolveError> {
+    let space = parse(strs, params.size()?)?;
+    let bytes = params.bytes()?;
+    let path = space
+        .shortest_path(bytes)
+        .ok_or(SolveError::Unreachable(bytes))?;
+    Ok(path.len() - 1)
+}
 
-    fn set_char(&mut self, p: &(usize, usize), v: char) {
-        self.grid[p.1][p.0] = v;
-    }
+pub fn solve_part2(strs: &[&str], params: Params) -> Result<(usize, usize), SolveError> {
+    Ok(first_blocking(strs, params, Search::UnionFind)?.byte)
+}
 
-    fn checked_shift(&self, from: &(usize, usize), dir: &Dir) -> Option<(usize, usize)> {
-        let (shift_x, shift_y) = dir.get_shift();
-        let new_x = from.0.checked_add_signed(shift_x)?;
-        let new_y = from.1.checked_add_signed(shift_y)?;
-        if self.is_in_bounds(new_x, new_y) && (self.get_char((new_x, new_y)) == self.free_loc_char)
-        {
-            Some((new_x, new_y))
-        } else {
-            None
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_fixture::{fixture, puzzle_tests, Fixture};
+
+    fn params(f: &Fixture) -> Params {
+        Params {
+            size: Some(f.param("size")),
+            bytes: Some(f.param("bytes")),
         }
     }
 
-    fn possible_moves(&self, p: (usize, usize)) -> impl Iterator<Item = Move> + '_ {
-        let moves = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
-        moves
-            .into_iter()
-            .filter_map(move |dir| self.checked_shift(&p, &dir))
-            .map(Move::new)
-    }
-    fn scan_iter(&self) -> impl Iterator<Item = (&char, usize, usize)> + '_ {
-        self.grid
-            .iter()
-            .enumerate()
-            .flat_map(|(y, line)| line.iter().enumerate().map(move |(x, ch)| (ch, x, y)))
+    #[test]
+    fn test_name() {
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines(), params(&f)).unwrap());
     }
 
-    fn empty_locs_iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
-        self.scan_iter().filter_map(|(ch, x, y)| {
-            if *ch == self.free_loc_char {
-                Some((x, y))
-            } else {
-                None
+    #[test]
+    fn test_params() {
+        let f = fixture!("example");
+        let input = f.lines();
+        let size = Params {
+            size: Some(7),
+            bytes: None,
+        };
+        assert_eq!(solve_part1(&input, size), Err(SolveError::Missing("bytes")));
+        assert_eq!(solve_part2(&input, size), Ok((6, 1)));
+        let bytes = Params {
+            size: None,
+            bytes: Some(12),
+        };
+        assert_eq!(solve_part1(&input, bytes), Err(SolveError::Missing("size")));
+        assert_eq!(solve_part1(&input, size.or(bytes)), Ok(22));
+        assert_eq!(
+            solve_part1(&input, Params::default())
+                .unwrap_err()
+                .to_string(),
+            "No size: pass --size or give it with the input"
+        );
+
+        let args = Params {
+            size: Some(9),
+            bytes: None,
+        };
+        assert_eq!(
+            args.or(params(&f)),
+            Params {
+                size: Some(9),
+                bytes: Some(12)
             }
-        })
+        );
     }
 
-    fn shortes_path(&self, from: (usize, usize), to: (usize, usize)) -> Option<usize> {
-        let mut dist: HashMap<(usize, usize), usize> = HashMap::new();
-        for loc in self.empty_locs_iter() {
-            dist.insert(loc, usize::MAX);
-        }
-        dist.insert(from, 0);
-
-        let mut heap = BinaryHeap::new();
-        heap.push(Move { cost: 0, p: from });
-
-        while let Some(Move { cost, p }) = heap.pop() {
-            if p == to {
-                return Some(cost);
-            }
-            if cost > *dist.get(&p).unwrap() {
-                continue;
-            }
-
-            for mv in self.possible_moves(p) {
-                let next = Move {
-                    cost: cost + mv.cost,
-                    p: mv.p,
-                };
-                if next.cost < *dist.get(&next.p).unwrap() {
-                    heap.push(next);
-                    dist.insert(next.p, next.cost);
-                }
-            }
-        }
-        None
+    #[test]
+    fn test_exit_errors() {
+        let input = fixture!("example").lines();
+        let all = Params {
+            size: Some(7),
+            bytes: Some(input.len()),
+        };
+        assert_eq!(solve_part1(&input, all), Err(SolveError::Unreachable(25)));
+        let open = &input[..12];
+        assert_eq!(solve_part2(open, all), Err(SolveError::NeverBlocked));
     }
-}
-
-fn parse_fall(strs: &[&str]) -> Vec<(usize, usize)> {
-    strs.iter()
-        .map(|s| {
-            let v: Vec<usize> = s.split(",").map(|x| x.parse().unwrap()).collect();
-            (v[0], v[1])
-        })
-        .collect()
-}
 
-pub fn solve_part1(strs: &[&str]) -> usize {
-    let fall = parse_fall(strs);
-    let mut w = World::new(71, 71, '.');
-    for loc in fall.iter().take(1024) {
-        w.set_char(&(loc.0, loc.1), '#');
-    }
-    let res = w.shortes_path((0, 0), (70, 70));
-    res.unwrap()
-}
-pub fn solve_part2(strs: &[&str]) -> (usize, usize) {
-    let fall = parse_fall(strs);
-    let mut w = World::new(71, 71, '.');
-    for loc in fall.iter().take(1024) {
-        w.set_char(&(loc.0, loc.1), '#');
+    #[test]
+    fn test_out_of_range() {
+        let input = fixture!("example").lines();
+        let err = parse(&input, 6).err().unwrap();
+        assert_eq!(err.line, 6);
+        assert_eq!(err.message, "Byte 6,3 is outside the 6x6 space");
+        let bytes = MemorySpace::parse_bytes(&input).unwrap();
+        assert!(MemorySpace::new(7, 6, bytes).is_err());
+        assert_eq!(
+            MemorySpace::parse_bytes(&["1,2", "3;4"])
+                .err()
+                .unwrap()
+                .line,
+            2
+        );
+        assert!(MemorySpace::parse_bytes(&["1,x"]).is_err());
     }
 
-    fall.into_iter()
-        .skip(1024)
-        .find(|loc| {
-            w.set_char(&(loc.0, loc.1), '#');
-            let res = w.shortes_path((0, 0), (70, 70));
-            res.is_none()
-        })
-        .unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE: &str = "
-        ...#...
-        ..#..#.
-        ....#..
-        ...#..#
-        ..#..#.
-        .#..#..
-        #.#....";
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.strip_prefix("\n")
-            .unwrap()
-            .lines()
-            .map(str::trim)
-            .collect()
+    #[test]
+    fn test_render() {
+        let input = fixture!("example").lines();
+        let space = parse(&input, 7).unwrap();
+        assert_eq!(space.render(12, &[]), fixture!("corrupted").lines());
+
+        let path = space.shortest_path(12).unwrap();
+        let rendered = space.render(12, &path);
+        let marked = rendered
+            .iter()
+            .flat_map(|l| l.bytes())
+            .filter(|b| *b == b'O');
+        assert_eq!(marked.count(), 23);
     }
 
     #[test]
-    fn test_name() {
-        let input = read_input(EXAMPLE);
-        let w = World::from_grid(&input, '.');
-        let res = w.shortes_path((0, 0), (6, 6));
-        assert_eq!(res, Some(22));
-    }
+    fn test_part2() {
+        let input = fixture!("example").lines();
+        let space = parse(&input, 7).unwrap();
+        let bisect = space.first_blocking(Search::Bisect).unwrap();
+        assert_eq!(bisect.byte, (6, 1));
+        assert_eq!(bisect.index, 20);
+        assert_eq!(bisect.last_path, space.shortest_path(20).unwrap());
+        assert_eq!(space.first_blocking(Search::UnionFind), Some(bisect));
+
+        let open = parse(&input[..12], 7).unwrap();
+        assert_eq!(open.first_blocking(Search::Bisect), None);
+        assert_eq!(open.first_blocking(Search::UnionFind), None);
+    }
+
+    puzzle_tests!(|f| solve_part1(&f.lines(), params(f)).unwrap(), |f| {
+        let (x, y) = solve_part2(&f.lines(), params(f)).unwrap();
+        format!("{},{}", x, y)
+    });
 }
@@ -0,0 +1,51 @@
+//! Towel patterns and the designs to make from them.
+
+use std::ops::RangeInclusive;
+
+use aoc_runner::rng::Rng;
+
+/// `towels` patterns and `designs` designs of lengths drawn from the given
+/// ranges, over `colours`. Patterns may repeat.
+pub fn onsen(
+    towels: usize,
+    towel_len: RangeInclusive<usize>,
+    designs: usize,
+    design_len: RangeInclusive<usize>,
+    colours: &str,
+    r: &mut Rng,
+) -> String {
+    let mut stripes = |n: usize, len: &RangeInclusive<usize>| -> Vec<String> {
+        (0..n)
+            .map(|_| {
+                let n = r.range(*len.start(), *len.end());
+                r.string(colours, n)
+            })
+            .collect()
+    };
+    let towels = stripes(towels, &towel_len);
+    let designs = stripes(designs, &design_len);
+    format!("{}\n\n{}", towels.join(", "), designs.join("\n"))
+}
+
+/// `size` designs of 20 to 60 stripes and 25 towels of two or three, over
+/// the puzzle's five colours, plus every single stripe but red. Whether a
+/// design can be made turns on its reds, so roughly one in five can.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let input = onsen(25, 2..=3, size, 20..=60, "wubrg", r);
+    input.replacen("\n\n", ", w, u, b, g\n\n", 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1_with, solve_part2, MATCHERS};
+
+    #[test]
+    fn test_generated_onsen() {
+        let input = generate(40, &mut Rng::new(19));
+        let lines: Vec<&str> = input.lines().collect();
+        let possible = solve_part1_with(MATCHERS[1].1, &lines);
+        assert!(0 < possible && possible < 40);
+        assert!(solve_part2(&lines) > possible);
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
+use aoc_runner::diff::Variant;
+
 fn rec_match<'a>(s: &'a str, pats: &Vec<&'a str>, memo: &mut HashMap<&'a str, usize>) -> usize {
     if let Some(v) = memo.get(s) {
         return *v;
@@ -63,12 +65,18 @@ fn parse_input<'a>(strs: &'a [&'a str]) -> (Vec<&'a str>, Vec<&'a str>) {
     (patterns, designs)
 }
 
-pub fn solve_part1(strs: &[&str]) -> usize {
+type Matcher = fn(&Vec<&str>, &str) -> bool;
+
+/// Whether a design can be made, for `--impl`: a plain depth-first search,
+/// or counting the arrangements with memoisation.
+pub const MATCHERS: &[Variant<Matcher>] = &[
+    ("stack", solve_one_bool),
+    ("memo", |pats, design| solve_one(pats, design) > 0),
+];
+
+pub fn solve_part1_with(matches: Matcher, strs: &[&str]) -> usize {
     let (patterns, designs) = parse_input(strs);
-    designs
-        .iter()
-        .filter(|des| solve_one_bool(&patterns, des))
-        .count()
+    designs.iter().filter(|des| matches(&patterns, des)).count()
 }
 
 pub fn solve_part2(strs: &[&str]) -> usize {
@@ -79,25 +87,41 @@ pub fn solve_part2(strs: &[&str]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generator::onsen;
+    use aoc_fixture::{fixture, puzzle_tests};
+    use aoc_runner::diff::Check;
 
-    const EXAMPLE: &str = "
-r, wr, b, g, bwu, rb, gb, br
+    fn solve_part1(strs: &[&str]) -> usize {
+        solve_part1_with(MATCHERS[0].1, strs)
+    }
 
-rrbgbr";
+    #[test]
+    fn test_name() {
+        let f = fixture!("example");
+        let input = f.lines();
+        f.assert_part1(solve_part1(&input));
+        f.assert_part2(solve_part2(&input));
+    }
 
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.strip_prefix("\n")
-            .unwrap()
-            .lines()
-            .map(str::trim)
-            .collect()
+    /// An empty pattern would match forever.
+    fn is_onsen(s: &str) -> bool {
+        let lines: Vec<&str> = s.lines().collect();
+        lines.len() >= 2 && lines[1].is_empty() && lines[0].split(',').all(|p| !p.trim().is_empty())
     }
 
     #[test]
-    fn test_name() {
-        let input = read_input(EXAMPLE);
-        let res = solve_part2(&input);
-        dbg!(res);
-        assert_eq!(res, 16);
+    fn test_matchers_agree() {
+        Check::new(MATCHERS)
+            .cases(500)
+            .valid(is_onsen)
+            .assert_agree(
+                |m, s| solve_part1_with(*m, &s.lines().collect::<Vec<_>>()),
+                |r| {
+                    let (towels, designs) = (r.range(1, 5), r.range(1, 4));
+                    onsen(towels, 1..=3, designs, 1..=12, "wub", r)
+                },
+            );
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
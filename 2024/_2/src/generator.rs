@@ -0,0 +1,51 @@
+//! Reactor reports: mostly safe runs of levels, some with one bad level.
+
+use aoc_runner::rng::Rng;
+
+fn report(r: &mut Rng) -> Vec<usize> {
+    let mut level = r.range(25, 75);
+    let up = r.chance(0.5);
+    let mut levels = vec![level];
+    for _ in 1..r.range(5, 8) {
+        let step = r.range(1, 3);
+        level = if up { level + step } else { level - step };
+        levels.push(level);
+    }
+    match r.below(3) {
+        0 => {
+            let i = r.below(levels.len());
+            levels[i] = r.range(1, 99);
+        }
+        1 => {
+            let i = r.below(levels.len() - 1);
+            levels.swap(i, i + 1);
+        }
+        _ => {}
+    }
+    levels
+}
+
+/// `size` reports of five to eight levels.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let levels: Vec<String> = report(r).iter().map(|l| l.to_string()).collect();
+            levels.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_reports() {
+        let input = generate(200, &mut Rng::new(2));
+        let lines: Vec<&str> = input.lines().collect();
+        let (safe, dampened) = (solve_part1(&lines), solve_part2(&lines));
+        assert!(0 < safe && safe < dampened && dampened < 200);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod generator;
+
 fn solve_report(str: &str) -> bool {
     let xs: Vec<u32> = str
         .split(" ")
@@ -75,8 +77,9 @@ fn solve_report_with_dampener(str: &str) -> bool {
         return true;
     }
 
-    if xs.len() == 3 && xs[0] != xs[1] && xs[1] != xs[2] {
-        return true;
+    // Too short for the window below; a pair is left after the removal.
+    if xs.len() == 3 {
+        return check_if_ok_without_one(xs).is_some();
     }
 
     let mut skip_element: Option<usize> = None;
@@ -139,6 +142,7 @@ pub fn solve_part2(strs: &[&str]) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_fixture::{fixture, puzzle_tests};
 
     const CASES: [(&str, bool); 6] = [
         ("7 6 4 2 1", true),
@@ -176,6 +180,14 @@ mod tests {
         assert_eq!(solve_part1(&CASES.map(|x| x.0)), FINAL_RESULT);
     }
 
+    #[test]
+    fn test_example() {
+        let f = fixture!("example");
+        let input = f.lines();
+        f.assert_part1(solve_part1(&input));
+        f.assert_part2(solve_part2(&input));
+    }
+
     #[test]
     fn test_all_is_good() {
         assert!(check_if_all_good(&[7, 6, 4, 2, 1]));
@@ -187,6 +199,15 @@ mod tests {
         assert!(!solve_report_with_dampener("15 15 13 7 4 3 2"));
     }
 
+    #[test]
+    fn test_short_reports_with_dampener() {
+        assert!(solve_report_with_dampener("1 9"));
+        assert!(!solve_report_with_dampener("1 1 1"));
+        assert!(!solve_report_with_dampener("1 5 9"));
+        assert!(solve_report_with_dampener("1 2 9"));
+        assert!(solve_report_with_dampener("7 7 8"));
+    }
+
     #[test]
     fn test_example_cases_with_with_dampener() {
         for (s, r) in CASES_WITH_DAMPENER {
@@ -199,4 +220,6 @@ mod tests {
             FINAL_RESULT_WITH_DAMPENER
         );
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
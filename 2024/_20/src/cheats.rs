@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+
+type Pos = (usize, usize);
+
+/// Picoseconds saved mapped to the number of cheats saving exactly that much.
+pub type Histogram = BTreeMap<usize, usize>;
+
+pub struct CheatAnalyzer {
+    width: usize,
+    height: usize,
+    path: Vec<Pos>,
+    /// Distance from the start along the track, `usize::MAX` off the track.
+    dist: Vec<usize>,
+}
+
+impl CheatAnalyzer {
+    pub fn new(width: usize, height: usize, path: Vec<Pos>) -> CheatAnalyzer {
+        let mut dist = vec![usize::MAX; width * height];
+        for (d, (x, y)) in path.iter().enumerate() {
+            dist[y * width + x] = d;
+        }
+        CheatAnalyzer {
+            width,
+            height,
+            path,
+            dist,
+        }
+    }
+
+    fn dist(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.dist[y * self.width + x]).filter(|d| *d != usize::MAX)
+    }
+
+    /// Every cheat lasting at most `radius` picoseconds that saves time,
+    /// found by checking the cells within Manhattan distance `radius` of each
+    /// track cell.
+    pub fn histogram(&self, radius: usize) -> Histogram {
+        let r = radius as isize;
+        let diamond: Vec<(isize, isize, usize)> = (-r..=r)
+            .flat_map(|dy| {
+                let w = r - dy.abs();
+                (-w..=w).map(move |dx| (dx, dy, (dx.abs() + dy.abs()) as usize))
+            })
+            .filter(|(_, _, len)| *len >= 2)
+            .collect();
+
+        let mut hist = Histogram::new();
+        for (d, (x, y)) in self.path.iter().enumerate() {
+            for (dx, dy, len) in diamond.iter() {
+                let Some(end) = x
+                    .checked_add_signed(*dx)
+                    .zip(y.checked_add_signed(*dy))
+                    .and_then(|(ex, ey)| self.dist(ex, ey))
+                else {
+                    continue;
+                };
+                if end > d + len {
+                    *hist.entry(end - d - len).or_insert(0) += 1;
+                }
+            }
+        }
+        hist
+    }
+
+    /// Same as [`CheatAnalyzer::histogram`], but by brute force over every
+    /// pair of track cells, taking the time saved from their positions along
+    /// the path rather than from the distance grid.
+    pub fn histogram_pairs(&self, radius: usize) -> Histogram {
+        let mut hist = Histogram::new();
+        for (d, (x, y)) in self.path.iter().enumerate() {
+            for (end, (ex, ey)) in self.path.iter().enumerate().skip(d + 1) {
+                let len = x.abs_diff(*ex) + y.abs_diff(*ey);
+                if (2..=radius).contains(&len) && end > d + len {
+                    *hist.entry(end - d - len).or_insert(0) += 1;
+                }
+            }
+        }
+        hist
+    }
+}
+
+/// The histogram as the puzzle lists it, skipping savings below `threshold`.
+pub fn format_histogram(hist: &Histogram, threshold: usize) -> Vec<String> {
+    hist.range(threshold..)
+        .map(|(saved, n)| {
+            if *n == 1 {
+                format!("There is one cheat that saves {} picoseconds.", saved)
+            } else {
+                format!("There are {} cheats that save {} picoseconds.", n, saved)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+//! Racetracks: one winding track through walls that never touches itself.
+
+use aoc_runner::rng::Rng;
+
+/// A `size` by `size` grid (rounded up to odd, at least 5). The track is the
+/// longest branch of a random depth-first spanning tree over the odd cells,
+/// so it doubles back on itself behind single walls.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let n = size.max(5) | 1;
+    let cells = (n - 1) / 2;
+    let id = |(x, y): (usize, usize)| y * cells + x;
+    let mut parent: Vec<Option<(usize, usize)>> = vec![None; cells * cells];
+    let mut seen = vec![false; cells * cells];
+
+    let root = (r.below(cells), r.below(cells));
+    seen[id(root)] = true;
+    let mut stack = vec![root];
+    let mut farthest = (root, 1);
+    while let Some(&(x, y)) = stack.last() {
+        let mut next: Vec<(usize, usize)> = [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .iter()
+            .filter_map(|(dx, dy)| Some((x.checked_add_signed(*dx)?, y.checked_add_signed(*dy)?)))
+            .filter(|(nx, ny)| *nx < cells && *ny < cells && !seen[id((*nx, *ny))])
+            .collect();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        r.shuffle(&mut next);
+        seen[id(next[0])] = true;
+        parent[id(next[0])] = Some((x, y));
+        stack.push(next[0]);
+        if stack.len() > farthest.1 {
+            farthest = (next[0], stack.len());
+        }
+    }
+
+    let mut grid = vec![vec!['#'; n]; n];
+    let at = |(x, y): (usize, usize)| (2 * x + 1, 2 * y + 1);
+    let mut cur = farthest.0;
+    let (ex, ey) = at(cur);
+    grid[ey][ex] = 'E';
+    while let Some(p) = parent[id(cur)] {
+        let ((cx, cy), (px, py)) = (at(cur), at(p));
+        grid[(cy + py) / 2][(cx + px) / 2] = '.';
+        grid[py][px] = '.';
+        cur = p;
+    }
+    let (sx, sy) = at(root);
+    grid[sy][sx] = 'S';
+    grid.into_iter()
+        .map(|l| l.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::analyzer;
+
+    #[test]
+    fn test_generated_track() {
+        let input = generate(41, &mut Rng::new(20));
+        let a = analyzer(&input.lines().collect::<Vec<_>>());
+        assert!(a.histogram(2).keys().any(|saved| *saved >= 20));
+    }
+}
@@ -1,28 +1,29 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::io;
 
+use aoc_runner::Day;
+
+mod cheats;
+mod generator;
 mod solution;
 
-use solution::{solve_part1, solve_part2};
+use solution::{report, solve_part1_with, solve_part2_with, HISTOGRAMS};
 
 fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v: Vec<&str> = v.iter().map(String::as_str).collect();
-    println!("Part1:");
-    let res = solve_part1(&v);
-    println!("{}", res);
-
-    let res = solve_part2(&v);
-
-    println!("Part2:");
-    println!("{:?}", res);
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    let histogram = day.variant(HISTOGRAMS);
+    day.part(1, move |i| solve_part1_with(histogram, &i.lines()));
+    day.part(2, move |i| solve_part2_with(histogram, &i.lines()));
+    day.extras(|i| {
+        for (cheat_n, name) in [(2, "Part1"), (20, "Part2")] {
+            println!("{} cheats saving at least 100 picoseconds:", name);
+            for line in report(&i.lines(), cheat_n, 100) {
+                println!("{}", line);
+            }
+        }
+    });
 
-    Ok(())
+    day.finish()
 }
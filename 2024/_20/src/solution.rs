@@ -1,7 +1,8 @@
-use std::{
-    collections::{HashMap, HashSet},
-    hash::Hash,
-};
+use std::collections::HashSet;
+
+use aoc_runner::diff::Variant;
+
+use crate::cheats::{format_histogram, CheatAnalyzer, Histogram};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum Dir {
@@ -22,48 +23,6 @@ impl Dir {
     }
 }
 
-#[derive(Debug)]
-struct RacePath {
-    path: Vec<(usize, usize)>,
-    dist: HashMap<(usize, usize), usize>,
-}
-
-impl RacePath {
-    fn new(path: Vec<(usize, usize)>) -> RacePath {
-        let mut dist = HashMap::new();
-        for (d, loc) in path.iter().enumerate() {
-            dist.insert(*loc, d);
-        }
-        RacePath { path, dist }
-    }
-
-    fn get_path_dist(&self, p: (usize, usize)) -> Option<usize> {
-        self.dist.get(&p).copied()
-    }
-}
-
-#[derive(Debug, Eq, PartialEq, Clone)]
-struct Cheat {
-    start: (usize, usize),
-    start_d: usize,
-    end: (usize, usize),
-    end_d: usize,
-}
-
-impl Hash for Cheat {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.start.hash(state);
-        self.end.hash(state);
-    }
-}
-
-impl Cheat {
-    fn time_saved(&self) -> usize {
-        let d = self.start.0.abs_diff(self.end.0) + (self.start.1).abs_diff(self.end.1) - 1;
-        self.end_d - (self.start_d + d + 1)
-    }
-}
-
 #[derive(Debug)]
 struct World {
     grid: Vec<Vec<char>>,
@@ -123,7 +82,7 @@ impl World {
             .find_map(|(ch, x, y)| if *ch == c { Some((x, y)) } else { None })
     }
 
-    fn race_path(&self, from: (usize, usize), to: (usize, usize)) -> RacePath {
+    fn race_path(&self, from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
         let mut stack: Vec<(usize, usize)> = Vec::new();
         let mut visited: HashSet<(usize, usize)> = HashSet::new();
         stack.push(from);
@@ -142,106 +101,133 @@ impl World {
                 }
             }
         }
-        RacePath::new(path)
+        path
     }
 }
 
-pub fn solve(strs: &[&str], cheat_n: usize) -> usize {
+pub fn analyzer(strs: &[&str]) -> CheatAnalyzer {
     let w = World::from_grid(strs);
     let start = w.find_char('S').unwrap();
     let end = w.find_char('E').unwrap();
-    let rt = w.race_path(start, end);
-
-    let mut cheats = vec![];
-    for (i, f) in rt.path.iter().enumerate() {
-        for t in rt.path.iter().skip(i) {
-            if t == f {
-                continue;
-            }
-            let f_d = rt.get_path_dist(*f);
-            let t_d = rt.get_path_dist(*t);
-            let ch = Cheat {
-                start: *f,
-                start_d: f_d.unwrap(),
-                end: *t,
-                end_d: t_d.unwrap(),
-            };
-
-            //let dist = w.shortes_path(*f, *t).unwrap();
-            let dist = f.0.abs_diff(t.0) + (f.1).abs_diff(t.1);
-
-            if ch.time_saved() >= 2 && dist <= cheat_n {
-                //println!("{:?} {:?} = {:?}, {:?} {:?} {}", f, t, dist, f_d, t_d, ch.time_saved());
-                cheats.push(ch);
-            }
-        }
-    }
-
-    let mut buckets: HashMap<usize, HashSet<Cheat>> = HashMap::new();
-
-    for cheat in cheats {
-        let ts = cheat.time_saved();
-        if ts > 0 {
-            buckets
-                .entry(ts)
-                .and_modify(|x| {
-                    x.insert(cheat.clone());
-                })
-                .or_insert(HashSet::from([cheat]));
-        }
-    }
+    CheatAnalyzer::new(w.x_range.end, w.y_range.end, w.race_path(start, end))
+}
 
-    let mut c = 0;
+pub fn report(strs: &[&str], cheat_n: usize, threshold: usize) -> Vec<String> {
+    format_histogram(&analyzer(strs).histogram(cheat_n), threshold)
+}
 
-    for (k, v) in buckets.iter() {
-        //println!("{} {}", k, v.len());
-        if *k >= 100 {
-            c += v.len();
-        }
-    }
-    c
+type Histogrammer = fn(&CheatAnalyzer, usize) -> Histogram;
+
+/// Ways of enumerating the cheats, for `--impl`.
+pub const HISTOGRAMS: &[Variant<Histogrammer>] = &[
+    ("diamond", CheatAnalyzer::histogram),
+    ("pairs", CheatAnalyzer::histogram_pairs),
+];
+
+pub fn solve_with(
+    histogram: Histogrammer,
+    strs: &[&str],
+    cheat_n: usize,
+    threshold: usize,
+) -> usize {
+    histogram(&analyzer(strs), cheat_n)
+        .range(threshold..)
+        .map(|(_, n)| n)
+        .sum()
 }
 
-pub fn solve_part1(strs: &[&str]) -> usize {
-    solve(strs, 2)
+pub fn solve_part1_with(histogram: Histogrammer, strs: &[&str]) -> usize {
+    solve_with(histogram, strs, 2, 100)
 }
 
-pub fn solve_part2(strs: &[&str]) -> usize {
-    solve(strs, 20)
+pub fn solve_part2_with(histogram: Histogrammer, strs: &[&str]) -> usize {
+    solve_with(histogram, strs, 20, 100)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generator;
+    use aoc_fixture::{fixture, puzzle_tests};
+    use aoc_runner::diff::Check;
 
-    const EXAMPLE: &str = "
-###############
-#...#...#.....#
-#.#.#.#.#.###.#
-#S#...#.#.#...#
-#######.#.#.###
-#######.#.#...#
-#######.#.###.#
-###..E#...#...#
-###.#######.###
-#...###...#...#
-#.#####.#.###.#
-#.#...#.#.#...#
-#.#.#.#.#.#.###
-#...#...#...###
-###############";
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.strip_prefix("\n")
-            .unwrap()
-            .lines()
-            .map(str::trim)
-            .collect()
+    fn solve(strs: &[&str], cheat_n: usize, threshold: usize) -> usize {
+        solve_with(HISTOGRAMS[0].1, strs, cheat_n, threshold)
     }
 
     #[test]
     fn test_name() {
-        let input = read_input(EXAMPLE);
-        solve_part1(&input);
+        let input = fixture!("example").lines();
+        assert_eq!(solve(&input, 2, 1), 44);
+        assert_eq!(solve(&input, 20, 50), 285);
     }
+
+    #[test]
+    fn test_report() {
+        let input = fixture!("example").lines();
+        let expected = vec![
+            "There are 14 cheats that save 2 picoseconds.",
+            "There are 14 cheats that save 4 picoseconds.",
+            "There are 2 cheats that save 6 picoseconds.",
+            "There are 4 cheats that save 8 picoseconds.",
+            "There are 2 cheats that save 10 picoseconds.",
+            "There are 3 cheats that save 12 picoseconds.",
+            "There is one cheat that saves 20 picoseconds.",
+            "There is one cheat that saves 36 picoseconds.",
+            "There is one cheat that saves 38 picoseconds.",
+            "There is one cheat that saves 40 picoseconds.",
+            "There is one cheat that saves 64 picoseconds.",
+        ];
+        assert_eq!(report(&input, 2, 0), expected);
+
+        let res = report(&input, 20, 50);
+        assert_eq!(res[0], "There are 32 cheats that save 50 picoseconds.");
+        assert_eq!(
+            res[res.len() - 1],
+            "There are 3 cheats that save 76 picoseconds."
+        );
+    }
+
+    /// One track from `S` to `E` that never touches itself, on walls.
+    fn is_racetrack(s: &str) -> bool {
+        let grid: Vec<Vec<char>> = s.lines().map(|l| l.chars().collect()).collect();
+        let track = |x: usize, y: usize| {
+            grid.get(y)
+                .and_then(|l| l.get(x))
+                .is_some_and(|c| *c != '#')
+        };
+        let count = |c| s.chars().filter(|ch| *ch == c).count();
+        !grid.is_empty()
+            && grid.iter().all(|l| l.len() == grid[0].len())
+            && s.chars().all(|c| "#.SE\n".contains(c))
+            && count('S') == 1
+            && count('E') == 1
+            && (0..grid.len()).all(|y| {
+                (0..grid[0].len()).all(|x| {
+                    let around = [(x + 1, y), (x, y + 1)]
+                        .into_iter()
+                        .chain(x.checked_sub(1).map(|x| (x, y)))
+                        .chain(y.checked_sub(1).map(|y| (x, y)));
+                    !track(x, y) || around.filter(|(x, y)| track(*x, *y)).count() <= 2
+                })
+            })
+    }
+
+    #[test]
+    fn test_histograms_agree() {
+        assert!(is_racetrack(&fixture!("example").lines().join("\n")));
+        Check::new(HISTOGRAMS).valid(is_racetrack).assert_agree(
+            |h, s| {
+                let a = analyzer(&s.lines().collect::<Vec<_>>());
+                (h(&a, 2), h(&a, 6))
+            },
+            |r| generator::generate(r.range(4, 12), r),
+        );
+    }
+
+    puzzle_tests!(|f| solve(&f.lines(), 2, f.param("threshold")), |f| solve(
+        &f.lines(),
+        20,
+        f.param("threshold")
+    ));
 }
@@ -0,0 +1,25 @@
+//! Door codes for the keypad chain.
+
+use aoc_runner::rng::Rng;
+
+/// `size` codes of three digits followed by `A`.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let codes: Vec<String> = (0..size)
+        .map(|_| format!("{}A", r.string("0123456789", 3)))
+        .collect();
+    codes.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_codes() {
+        let input = generate(5, &mut Rng::new(21));
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(solve_part1(&lines).unwrap() < solve_part2(&lines).unwrap());
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+};
+
+pub const NUMERIC: &str = "
+789
+456
+123
+#0A";
+
+pub const DIRECTIONAL: &str = "
+#^A
+<v>";
+
+/// Arm moves and the directional keys that make them.
+const MOVES: [(char, (isize, isize)); 4] =
+    [('^', (0, -1)), ('v', (0, 1)), ('<', (-1, 0)), ('>', (1, 0))];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    NoA,
+    Repeated(char),
+    /// No path of keys leads from the first key to the second, or every
+    /// path needs a direction the keypad below lacks.
+    Disconnected(char, char),
+    /// A code asks for a key the keypad does not have.
+    UnknownKey(char),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::NoA => write!(f, "Keypad has no A button"),
+            LayoutError::Repeated(ch) => write!(f, "Key {:?} appears twice", ch),
+            LayoutError::Disconnected(a, b) => {
+                write!(f, "No route from key {:?} to key {:?}", a, b)
+            }
+            LayoutError::UnknownKey(ch) => write!(f, "Key {:?} is not on the keypad", ch),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+/// A keypad layout. `#` or a space marks a gap the arm must never cross.
+#[derive(Debug, Clone)]
+pub struct Keypad {
+    keys: Vec<(char, (isize, isize))>,
+    index: HashMap<char, usize>,
+}
+
+impl Keypad {
+    pub fn parse(layout: &str) -> Result<Keypad, LayoutError> {
+        let keys: Vec<(char, (isize, isize))> = layout
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .enumerate()
+            .flat_map(|(y, l)| {
+                l.chars()
+                    .enumerate()
+                    .map(move |(x, ch)| (ch, (x as isize, y as isize)))
+            })
+            .filter(|(ch, _)| *ch != '#' && *ch != ' ')
+            .collect();
+        let mut index = HashMap::new();
+        for (i, (ch, _)) in keys.iter().enumerate() {
+            if index.insert(*ch, i).is_some() {
+                return Err(LayoutError::Repeated(*ch));
+            }
+        }
+        if !index.contains_key(&'A') {
+            return Err(LayoutError::NoA);
+        }
+        Ok(Keypad { keys, index })
+    }
+
+    fn pos(&self, ch: char) -> (isize, isize) {
+        let i = *self
+            .index
+            .get(&ch)
+            .unwrap_or_else(|| panic!("No key {:?}", ch));
+        self.keys[i].1
+    }
+
+    fn is_key(&self, p: (isize, isize)) -> bool {
+        self.keys.iter().any(|(_, kp)| *kp == p)
+    }
+
+    /// Presses on the controlling keypad that move the arm from `from` to
+    /// `to` along any shortest path through keys and press it, in sorted
+    /// order; none if `to` cannot be reached.
+    fn routes(&self, from: char, to: char) -> Vec<String> {
+        let step = |(x, y): (isize, isize), (dx, dy): (isize, isize)| (x + dx, y + dy);
+        let target = self.pos(to);
+        let mut dist = HashMap::from([(target, 0)]);
+        let mut queue = VecDeque::from([target]);
+        while let Some(p) = queue.pop_front() {
+            for (_, d) in MOVES {
+                let q = step(p, d);
+                if self.is_key(q) && !dist.contains_key(&q) {
+                    dist.insert(q, dist[&p] + 1);
+                    queue.push_back(q);
+                }
+            }
+        }
+
+        let mut res = vec![];
+        let start = self.pos(from);
+        if !dist.contains_key(&start) {
+            return res;
+        }
+        // Every move towards `to` that gets one step closer stays shortest.
+        let mut paths = vec![(start, String::new())];
+        while let Some((p, route)) = paths.pop() {
+            if p == target {
+                res.push(route + "A");
+                continue;
+            }
+            for (ch, d) in MOVES {
+                let q = step(p, d);
+                if dist.get(&q) == Some(&(dist[&p] - 1)) {
+                    paths.push((q, format!("{}{}", route, ch)));
+                }
+            }
+        }
+        res.sort();
+        res
+    }
+
+    /// Keys pressed on this keypad when its arm, starting at `A`, is driven
+    /// by `presses` on a directional keypad. `None` if the arm hits a gap.
+    pub fn simulate(&self, presses: &str) -> Option<String> {
+        let mut p = self.pos('A');
+        let mut out = String::new();
+        for ch in presses.chars() {
+            match ch {
+                'A' => {
+                    let (key, _) = self.keys.iter().find(|(_, kp)| *kp == p)?;
+                    out.push(*key);
+                    continue;
+                }
+                _ => {
+                    let (_, (dx, dy)) = MOVES
+                        .iter()
+                        .find(|(m, _)| *m == ch)
+                        .unwrap_or_else(|| panic!("Unknown press {:?}", ch));
+                    p = (p.0 + dx, p.1 + dy);
+                }
+            }
+            if !self.is_key(p) {
+                return None;
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Keypads from the door down to the one the human presses directly; every
+/// keypad but the first is directional and drives the arm of the one above.
+pub struct KeypadChain {
+    pads: Vec<Keypad>,
+    /// `costs[i][a][b]`: human presses needed to move the arm of pad `i` from
+    /// key `a` to key `b` and press it.
+    costs: Vec<Vec<Vec<usize>>>,
+    /// `best[i][a][b]`: presses on pad `i + 1` achieving `costs[i][a][b]`;
+    /// empty for the human's keypad.
+    best: Vec<Vec<Vec<String>>>,
+}
+
+impl KeypadChain {
+    /// Fails if some pair of keys on a pad cannot be reached from one
+    /// another with the keys of the pad below it.
+    pub fn new(pads: Vec<Keypad>) -> Result<KeypadChain, LayoutError> {
+        assert!(!pads.is_empty(), "Empty keypad chain");
+        let n = pads.len();
+        let mut costs: Vec<Vec<Vec<usize>>> = vec![vec![]; n];
+        let mut best: Vec<Vec<Vec<String>>> = vec![vec![]; n];
+
+        let last = &pads[n - 1];
+        costs[n - 1] = vec![vec![1; last.keys.len()]; last.keys.len()];
+
+        for i in (0..n - 1).rev() {
+            let (pad, below) = (&pads[i], &pads[i + 1]);
+            let below_cost = |s: &str| -> Option<usize> {
+                let mut prev = below.index[&'A'];
+                s.chars()
+                    .map(|ch| {
+                        let next = *below.index.get(&ch)?;
+                        let c = costs[i + 1][prev][next];
+                        prev = next;
+                        Some(c)
+                    })
+                    .sum()
+            };
+            let mut layer_costs = vec![vec![0; pad.keys.len()]; pad.keys.len()];
+            let mut layer_best = vec![vec![String::new(); pad.keys.len()]; pad.keys.len()];
+            for (a, (from, _)) in pad.keys.iter().enumerate() {
+                for (b, (to, _)) in pad.keys.iter().enumerate() {
+                    let (c, route) = pad
+                        .routes(*from, *to)
+                        .into_iter()
+                        .filter_map(|r| Some((below_cost(&r)?, r)))
+                        .min()
+                        .ok_or(LayoutError::Disconnected(*from, *to))?;
+                    layer_costs[a][b] = c;
+                    layer_best[a][b] = route;
+                }
+            }
+            costs[i] = layer_costs;
+            best[i] = layer_best;
+        }
+        Ok(KeypadChain { pads, costs, best })
+    }
+
+    /// The door's numeric keypad driven through `robots` directional keypads
+    /// operated by robots, plus the directional keypad the human presses.
+    pub fn puzzle(robots: usize) -> KeypadChain {
+        let mut pads = vec![Keypad::parse(NUMERIC).unwrap()];
+        pads.extend((0..=robots).map(|_| Keypad::parse(DIRECTIONAL).unwrap()));
+        KeypadChain::new(pads).expect("Puzzle keypads are connected")
+    }
+
+    /// Position of `ch` on the `i`th keypad of the chain.
+    fn key(&self, i: usize, ch: char) -> Result<usize, LayoutError> {
+        self.pads[i]
+            .index
+            .get(&ch)
+            .copied()
+            .ok_or(LayoutError::UnknownKey(ch))
+    }
+
+    /// Length of the shortest human sequence that types `code` on the door.
+    pub fn presses(&self, code: &str) -> Result<usize, LayoutError> {
+        let mut prev = self.key(0, 'A')?;
+        code.chars()
+            .map(|ch| {
+                let next = self.key(0, ch)?;
+                let c = self.costs[0][prev][next];
+                prev = next;
+                Ok(c)
+            })
+            .sum()
+    }
+
+    /// Drives the chain with the human's `presses` and returns what gets
+    /// typed on the door, or `None` if some arm would hit a gap.
+    pub fn replay(&self, presses: &str) -> Option<String> {
+        let mut seq = presses.to_string();
+        for pad in self.pads[..self.pads.len() - 1].iter().rev() {
+            seq = pad.simulate(&seq)?;
+        }
+        Some(seq)
+    }
+
+    /// One cheapest sequence typed on every keypad, starting with `code`
+    /// itself and ending with what the human presses. Lengths grow
+    /// exponentially, so this is only practical for short chains.
+    pub fn sequences(&self, code: &str) -> Result<Vec<String>, LayoutError> {
+        let mut res = vec![code.to_string()];
+        for i in 0..self.pads.len() - 1 {
+            let mut prev = self.key(i, 'A')?;
+            let mut next_seq = String::new();
+            for ch in res[i].chars() {
+                let next = self.key(i, ch)?;
+                next_seq.push_str(&self.best[i][prev][next]);
+                prev = next;
+            }
+            res.push(next_seq);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes() {
+        let num = Keypad::parse(NUMERIC).unwrap();
+        assert_eq!(num.routes('A', '0'), vec!["<A"]);
+        assert_eq!(num.routes('A', '1'), vec!["<^<A", "^<<A"]);
+        assert_eq!(num.routes('1', 'A'), vec![">>vA", ">v>A"]);
+        assert_eq!(num.routes('2', '9'), vec![">^^A", "^>^A", "^^>A"]);
+        assert_eq!(num.routes('5', '5'), vec!["A"]);
+    }
+
+    #[test]
+    fn test_simulate() {
+        let num = Keypad::parse(NUMERIC).unwrap();
+        let dir = Keypad::parse(DIRECTIONAL).unwrap();
+        assert_eq!(num.simulate("<A^A>^^AvvvA").as_deref(), Some("029A"));
+        assert_eq!(num.simulate("<<A"), None);
+        let seq = "<vA<AA>>^AvAA<^A>A<v<A>>^AvA^A<vA>^A<v<A>^A>AAvA^A<v<A>A>^AAAvA<^A>A";
+        let two = dir.simulate(seq).unwrap();
+        let one = dir.simulate(&two).unwrap();
+        assert_eq!(num.simulate(&one).as_deref(), Some("029A"));
+    }
+
+    #[test]
+    fn test_sequences() {
+        let chain = KeypadChain::puzzle(2);
+        let num = Keypad::parse(NUMERIC).unwrap();
+        let dir = Keypad::parse(DIRECTIONAL).unwrap();
+        for (code, len) in [
+            ("029A", 68),
+            ("980A", 60),
+            ("179A", 68),
+            ("456A", 64),
+            ("379A", 64),
+        ] {
+            let seqs = chain.sequences(code).unwrap();
+            assert_eq!(seqs.len(), 4);
+            assert_eq!(seqs[3].len(), len);
+            assert_eq!(chain.presses(code), Ok(len));
+
+            let two = dir.simulate(&seqs[3]).unwrap();
+            assert_eq!(two, seqs[2]);
+            let one = dir.simulate(&two).unwrap();
+            assert_eq!(num.simulate(&one).as_deref(), Some(code));
+            assert_eq!(chain.replay(&seqs[3]).as_deref(), Some(code));
+        }
+    }
+
+    #[test]
+    fn test_custom_layout() {
+        let dir = || Keypad::parse(DIRECTIONAL).unwrap();
+        let pad = Keypad::parse("AB\n C").unwrap();
+        let chain = KeypadChain::new(vec![pad, dir()]).unwrap();
+        assert_eq!(chain.sequences("CA").unwrap(), vec!["CA", ">vA^<A"]);
+        assert_eq!(chain.presses("CA"), Ok(6));
+
+        // Both corners between A and D are gaps, but the keys still connect.
+        let pad = Keypad::parse("A#\nBC\n#D").unwrap();
+        assert_eq!(pad.routes('A', 'D'), vec!["v>vA"]);
+        let chain = KeypadChain::new(vec![pad, dir(), dir()]).unwrap();
+        let seqs = chain.sequences("DA").unwrap();
+        assert_eq!(seqs[1], "v>vA^<^A");
+        assert_eq!(chain.replay(&seqs[2]).as_deref(), Some("DA"));
+    }
+
+    #[test]
+    fn test_layout_errors() {
+        let dir = || Keypad::parse(DIRECTIONAL).unwrap();
+        assert_eq!(Keypad::parse("12\n34").unwrap_err(), LayoutError::NoA);
+        assert_eq!(
+            Keypad::parse("A1\n1B").unwrap_err(),
+            LayoutError::Repeated('1')
+        );
+        let split = Keypad::parse("A#B").unwrap();
+        assert!(split.routes('A', 'B').is_empty());
+        assert_eq!(
+            KeypadChain::new(vec![split, dir()]).err(),
+            Some(LayoutError::Disconnected('A', 'B'))
+        );
+        // The pad below has no `>`, so the arm above can never move right.
+        let lefty = Keypad::parse("^A\n<v").unwrap();
+        let pad = Keypad::parse("AB").unwrap();
+        assert_eq!(
+            KeypadChain::new(vec![pad, lefty]).err(),
+            Some(LayoutError::Disconnected('A', 'B'))
+        );
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        let chain = KeypadChain::puzzle(2);
+        assert_eq!(chain.presses("02x9A"), Err(LayoutError::UnknownKey('x')));
+        assert_eq!(
+            chain.sequences("O29A").unwrap_err().to_string(),
+            "Key 'O' is not on the keypad"
+        );
+    }
+}
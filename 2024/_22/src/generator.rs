@@ -0,0 +1,26 @@
+//! Buyers' initial secret numbers.
+
+use aoc_runner::rng::Rng;
+
+/// `size` secrets below 2^24.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let secrets: Vec<String> = (0..size)
+        .map(|_| r.range(1, (1 << 24) - 1).to_string())
+        .collect();
+    secrets.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_secrets() {
+        let input = generate(50, &mut Rng::new(22));
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 50);
+        assert!(solve_part1(&lines) > 0);
+        assert!(solve_part2(&lines) > 0);
+    }
+}
@@ -0,0 +1,144 @@
+pub fn mix(secret: u64, number: u64) -> u64 {
+    number ^ secret
+}
+
+pub fn prune(secret: u64) -> u64 {
+    secret % 16777216
+}
+
+pub fn next_secret(secret: u64) -> u64 {
+    let s0 = secret * 64;
+    let s0 = prune(mix(secret, s0));
+
+    let s1 = s0 / 32;
+    let s1 = prune(mix(s0, s1));
+
+    let s2 = s1 * 2048;
+    prune(mix(s1, s2))
+}
+
+/// Secrets generated by a buyer, starting with the one after `seed`.
+#[derive(Debug, Clone)]
+pub struct Secrets(u64);
+
+impl Secrets {
+    pub fn new(seed: u64) -> Secrets {
+        Secrets(seed)
+    }
+}
+
+impl Iterator for Secrets {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        self.0 = next_secret(self.0);
+        Some(self.0)
+    }
+}
+
+fn price(secret: u64) -> u32 {
+    (secret % 10) as u32
+}
+
+/// Price changes lie in `-9..=9`, so four of them form a base-19 number.
+pub const SEQS: usize = 19 * 19 * 19 * 19;
+
+pub type Seq = [i8; 4];
+
+pub fn seq_index(seq: Seq) -> usize {
+    seq.iter().fold(0, |acc, d| acc * 19 + (*d + 9) as usize)
+}
+
+pub fn seq_from_index(mut idx: usize) -> Seq {
+    let mut seq = [0; 4];
+    for d in seq.iter_mut().rev() {
+        *d = (idx % 19) as i8 - 9;
+        idx /= 19;
+    }
+    seq
+}
+
+/// Bananas collected for every possible sequence of four price changes,
+/// summed over all buyers.
+pub struct Market {
+    totals: Box<[u32; SEQS]>,
+}
+
+impl Market {
+    pub fn new(seeds: &[u64], n: usize) -> Market {
+        let mut totals: Box<[u32; SEQS]> = vec![0; SEQS].try_into().unwrap();
+        let mut seen = vec![0u64; SEQS.div_ceil(64)];
+
+        for seed in seeds {
+            seen.fill(0);
+            let mut idx = 0;
+            let mut prev = price(*seed);
+            for (i, secret) in Secrets::new(*seed).take(n).enumerate() {
+                let p = price(secret);
+                let delta = p as usize + 9 - prev as usize;
+                idx = (idx * 19 + delta) % SEQS;
+                prev = p;
+                if i < 3 {
+                    continue;
+                }
+                let (word, bit) = (idx / 64, 1 << (idx % 64));
+                // A monkey sells on the first occurrence only.
+                if seen[word] & bit == 0 {
+                    seen[word] |= bit;
+                    totals[idx] += p;
+                }
+            }
+        }
+        Market { totals }
+    }
+
+    pub fn bananas(&self, seq: Seq) -> u32 {
+        self.totals[seq_index(seq)]
+    }
+
+    pub fn best(&self) -> (Seq, u32) {
+        self.top(1)[0]
+    }
+
+    /// The `k` most profitable sequences, best first.
+    pub fn top(&self, k: usize) -> Vec<(Seq, u32)> {
+        let mut idx: Vec<usize> = (0..SEQS).collect();
+        idx.sort_by_key(|i| (std::cmp::Reverse(self.totals[*i]), *i));
+        idx.into_iter()
+            .take(k)
+            .map(seq_from_index)
+            .map(|seq| (seq, self.bananas(seq)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secrets() {
+        let s: Vec<u64> = Secrets::new(123).take(3).collect();
+        assert_eq!(s, vec![15887950, 16495136, 527345]);
+        assert_eq!(Secrets::new(123).nth(9), Some(5908254));
+        assert_eq!(Secrets::new(1).nth(1999), Some(8685429));
+    }
+
+    #[test]
+    fn test_seq_index() {
+        assert_eq!(seq_index([-9, -9, -9, -9]), 0);
+        assert_eq!(seq_index([9, 9, 9, 9]), SEQS - 1);
+        assert_eq!(seq_from_index(seq_index([-2, 1, -1, 3])), [-2, 1, -1, 3]);
+    }
+
+    #[test]
+    fn test_market() {
+        let m = Market::new(&[123], 9);
+        assert_eq!(m.bananas([-1, -1, 0, 2]), 6);
+
+        let m = Market::new(&[1, 2, 3, 2024], 2000);
+        assert_eq!(m.best(), ([-2, 1, -1, 3], 23));
+        assert_eq!(m.top(3)[0], m.best());
+        assert!(m.top(3).windows(2).all(|w| w[0].1 >= w[1].1));
+    }
+}
@@ -0,0 +1,61 @@
+//! LAN party maps: a sparse random network hiding one large clique.
+
+use std::collections::BTreeSet;
+
+use aoc_runner::rng::Rng;
+
+/// Two-letter computer names, all 676 of them, in a random order.
+fn names(r: &mut Rng) -> Vec<String> {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let mut names: Vec<String> = letters
+        .iter()
+        .flat_map(|a| letters.iter().map(move |b| format!("{}{}", a, b)))
+        .collect();
+    r.shuffle(&mut names);
+    names
+}
+
+/// `size` computers (at most 676) with about thirteen links each, thirteen
+/// of which are all linked to one another.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let n = size.clamp(2, 26 * 26);
+    let names = names(r);
+    let mut edges: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let clique = n.min(13);
+    for a in 0..clique {
+        for b in a + 1..clique {
+            edges.insert((a, b));
+        }
+    }
+    for _ in 0..n * 6 {
+        let (a, b) = (r.below(n), r.below(n));
+        if a != b {
+            edges.insert((a.min(b), a.max(b)));
+        }
+    }
+
+    let mut lines: Vec<String> = edges
+        .into_iter()
+        .map(|(a, b)| match r.chance(0.5) {
+            true => format!("{}-{}", names[a], names[b]),
+            false => format!("{}-{}", names[b], names[a]),
+        })
+        .collect();
+    r.shuffle(&mut lines);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_network() {
+        let input = generate(200, &mut Rng::new(23));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(lines.len() > 1000);
+        assert!(solve_part1(&lines) > 0);
+        assert!(solve_part2(&lines).split(',').count() >= 13);
+    }
+}
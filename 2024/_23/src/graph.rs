@@ -0,0 +1,326 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+};
+
+pub type NodeId = u16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> BitSet {
+        BitSet {
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    pub fn insert(&mut self, i: NodeId) {
+        self.words[i as usize / 64] |= 1 << (i % 64);
+    }
+
+    pub fn remove(&mut self, i: NodeId) {
+        self.words[i as usize / 64] &= !(1 << (i % 64));
+    }
+
+    pub fn contains(&self, i: NodeId) -> bool {
+        self.words[i as usize / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn and(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+
+    pub fn and_not(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & !b)
+                .collect(),
+        }
+    }
+
+    pub fn or(&self, other: &BitSet) -> BitSet {
+        BitSet {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a | b)
+                .collect(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, w)| {
+            let mut w = *w;
+            std::iter::from_fn(move || {
+                if w == 0 {
+                    return None;
+                }
+                let bit = w.trailing_zeros();
+                w &= w - 1;
+                Some((i * 64) as NodeId + bit as NodeId)
+            })
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    /// Degree mapped to the number of nodes having it.
+    pub histogram: BTreeMap<usize, usize>,
+}
+
+/// Undirected graph over interned node names with one adjacency bitset per node.
+#[derive(Debug)]
+pub struct Graph {
+    names: Vec<String>,
+    edges: Vec<(NodeId, NodeId)>,
+    adj: Vec<BitSet>,
+}
+
+impl Graph {
+    /// Parses `a-b` lines, interning names in order of first appearance.
+    pub fn parse(strs: &[&str]) -> Graph {
+        let mut names: Vec<String> = vec![];
+        let mut ids: HashMap<String, NodeId> = HashMap::new();
+        let mut intern = |name: &str| -> NodeId {
+            *ids.entry(name.to_string()).or_insert_with(|| {
+                names.push(name.to_string());
+                NodeId::try_from(names.len() - 1).expect("Too many nodes")
+            })
+        };
+        let edges: Vec<(NodeId, NodeId)> = strs
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                let (a, b) = s.split_once('-').expect("Edge is not a-b");
+                (intern(a), intern(b))
+            })
+            .collect();
+
+        let mut adj = vec![BitSet::new(names.len()); names.len()];
+        for (a, b) in edges.iter() {
+            adj[*a as usize].insert(*b);
+            adj[*b as usize].insert(*a);
+        }
+        Graph { names, edges, adj }
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id as usize]
+    }
+
+    fn all(&self) -> BitSet {
+        let mut s = BitSet::new(self.len());
+        for i in 0..self.len() {
+            s.insert(i as NodeId);
+        }
+        s
+    }
+
+    fn neighbours(&self, id: NodeId) -> &BitSet {
+        &self.adj[id as usize]
+    }
+
+    /// Node names of `ids`, sorted.
+    pub fn names(&self, ids: &[NodeId]) -> Vec<&str> {
+        let mut v: Vec<&str> = ids.iter().map(|i| self.name(*i)).collect();
+        v.sort();
+        v
+    }
+
+    /// Every maximal clique, by Bron–Kerbosch with Tomita pivoting.
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let mut res = vec![];
+        self.bron_kerbosch(&mut vec![], self.all(), BitSet::new(self.len()), &mut res);
+        res
+    }
+
+    fn bron_kerbosch(
+        &self,
+        r: &mut Vec<NodeId>,
+        mut p: BitSet,
+        mut x: BitSet,
+        res: &mut Vec<Vec<NodeId>>,
+    ) {
+        if p.is_empty() {
+            if x.is_empty() {
+                res.push(r.clone());
+            }
+            return;
+        }
+        // Neighbours of the pivot are covered by the branches of its
+        // non-neighbours, so only those need exploring.
+        let pivot = p
+            .or(&x)
+            .iter()
+            .max_by_key(|u| p.and(self.neighbours(*u)).len())
+            .unwrap();
+        for v in p.and_not(self.neighbours(pivot)).iter().collect::<Vec<_>>() {
+            let n = self.neighbours(v);
+            r.push(v);
+            self.bron_kerbosch(r, p.and(n), x.and(n), res);
+            r.pop();
+            p.remove(v);
+            x.insert(v);
+        }
+    }
+
+    pub fn max_clique(&self) -> Vec<NodeId> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(|c| c.len())
+            .unwrap_or_default()
+    }
+
+    /// Every clique of exactly `k` nodes containing at least one node whose
+    /// name satisfies `pred`. Each clique is listed once, in increasing id order.
+    pub fn k_cliques(&self, k: usize, pred: impl Fn(&str) -> bool) -> Vec<Vec<NodeId>> {
+        let mut res = vec![];
+        let mut clique = vec![];
+        self.extend_clique(k, &self.all(), &mut clique, &pred, &mut res);
+        res
+    }
+
+    fn extend_clique(
+        &self,
+        k: usize,
+        candidates: &BitSet,
+        clique: &mut Vec<NodeId>,
+        pred: &impl Fn(&str) -> bool,
+        res: &mut Vec<Vec<NodeId>>,
+    ) {
+        if clique.len() == k {
+            if clique.iter().any(|i| pred(self.name(*i))) {
+                res.push(clique.clone());
+            }
+            return;
+        }
+        for v in candidates.iter() {
+            let mut next = candidates.and(self.neighbours(v));
+            for u in next.clone().iter().take_while(|u| *u <= v) {
+                next.remove(u);
+            }
+            clique.push(v);
+            self.extend_clique(k, &next, clique, pred, res);
+            clique.pop();
+        }
+    }
+
+    pub fn degree_stats(&self) -> DegreeStats {
+        let degrees: Vec<usize> = self.adj.iter().map(BitSet::len).collect();
+        let mut histogram = BTreeMap::new();
+        for d in degrees.iter() {
+            *histogram.entry(*d).or_insert(0) += 1;
+        }
+        DegreeStats {
+            min: degrees.iter().copied().min().unwrap_or(0),
+            max: degrees.iter().copied().max().unwrap_or(0),
+            mean: degrees.iter().sum::<usize>() as f64 / degrees.len().max(1) as f64,
+            histogram,
+        }
+    }
+
+    /// Graphviz source with the nodes and edges of `highlight` drawn in red.
+    pub fn to_dot(&self, highlight: &[NodeId]) -> String {
+        let mut marked = BitSet::new(self.len());
+        for i in highlight {
+            marked.insert(*i);
+        }
+        let mut s = String::from("graph lan {\n");
+        for i in marked.iter() {
+            writeln!(s, "  {} [style=filled, fillcolor=red];", self.name(i)).unwrap();
+        }
+        for (a, b) in self.edges.iter() {
+            write!(s, "  {} -- {}", self.name(*a), self.name(*b)).unwrap();
+            if marked.contains(*a) && marked.contains(*b) {
+                s.push_str(" [color=red, penwidth=2]");
+            }
+            s.push_str(";\n");
+        }
+        s.push_str("}\n");
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset() {
+        let mut s = BitSet::new(130);
+        s.insert(3);
+        s.insert(64);
+        s.insert(129);
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![3, 64, 129]);
+        assert_eq!(s.len(), 3);
+        s.remove(64);
+        assert!(!s.contains(64));
+        assert!(s.contains(129));
+    }
+
+    #[test]
+    fn test_cliques() {
+        let g = Graph::parse(&["a-b", "b-c", "c-a", "c-d", "d-e"]);
+        let mut cliques: Vec<Vec<&str>> = g.maximal_cliques().iter().map(|c| g.names(c)).collect();
+        cliques.sort();
+        assert_eq!(
+            cliques,
+            vec![vec!["a", "b", "c"], vec!["c", "d"], vec!["d", "e"]]
+        );
+        assert_eq!(g.k_cliques(2, |_| true).len(), 5);
+        assert_eq!(g.k_cliques(2, |n| n == "e").len(), 1);
+        assert_eq!(g.k_cliques(3, |_| true).len(), 1);
+        assert!(g.k_cliques(4, |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_degree_stats() {
+        let g = Graph::parse(&["a-b", "b-c", "c-a", "c-d"]);
+        let stats = g.degree_stats();
+        assert_eq!(stats.min, 1);
+        assert_eq!(stats.max, 3);
+        assert_eq!(stats.mean, 2.0);
+        assert_eq!(stats.histogram, BTreeMap::from([(1, 1), (2, 2), (3, 1)]));
+    }
+
+    #[test]
+    fn test_dot() {
+        let g = Graph::parse(&["a-b", "b-c"]);
+        // Ids are handed out in order of first appearance.
+        let dot = g.to_dot(&[0, 1]);
+        assert_eq!(
+            dot,
+            "graph lan {\n  a [style=filled, fillcolor=red];\n  b [style=filled, fillcolor=red];\n  a -- b [color=red, penwidth=2];\n  b -- c;\n}\n"
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! Netlists of bitwise gates over fixed-width signals.
+//!
+//! One line per driven wire, in either of the two puzzle dialects:
+//!
+//! ```text
+//! x00: 1            x AND y -> d
+//! 123 -> x          x LSHIFT 2 -> f
+//! a -> b            NOT x -> h
+//! ```
+//!
+//! Operands are wire names or decimal constants. Wires are evaluated on
+//! demand and remembered until a wire is overridden with [`Netlist::set`].
+
+use std::{collections::HashMap, fmt};
+
+pub type WireId = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    Wire(WireId),
+    Const(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    And,
+    Or,
+    Xor,
+    LShift,
+    RShift,
+}
+
+impl BinOp {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BinOp::And => "AND",
+            BinOp::Or => "OR",
+            BinOp::Xor => "XOR",
+            BinOp::LShift => "LSHIFT",
+            BinOp::RShift => "RSHIFT",
+        }
+    }
+
+    fn parse(s: &str) -> Option<BinOp> {
+        [
+            BinOp::And,
+            BinOp::Or,
+            BinOp::Xor,
+            BinOp::LShift,
+            BinOp::RShift,
+        ]
+        .into_iter()
+        .find(|op| op.name() == s)
+    }
+
+    /// Shifts past the width clear every bit rather than wrapping.
+    fn apply(&self, a: u64, b: u64) -> u64 {
+        let shift = u32::try_from(b).unwrap_or(u32::MAX);
+        match self {
+            BinOp::And => a & b,
+            BinOp::Or => a | b,
+            BinOp::Xor => a ^ b,
+            BinOp::LShift => a.checked_shl(shift).unwrap_or(0),
+            BinOp::RShift => a.checked_shr(shift).unwrap_or(0),
+        }
+    }
+}
+
+/// What drives a wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gate {
+    Assign(Operand),
+    Not(Operand),
+    Binary(Operand, BinOp, Operand),
+}
+
+impl Gate {
+    pub fn inputs(&self) -> impl Iterator<Item = WireId> {
+        let (a, b) = match *self {
+            Gate::Assign(a) | Gate::Not(a) => (a, None),
+            Gate::Binary(a, _, b) => (a, Some(b)),
+        };
+        [Some(a), b].into_iter().flatten().filter_map(|o| match o {
+            Operand::Wire(w) => Some(w),
+            Operand::Const(_) => None,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line in the input.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum EvalError {
+    Unknown(String),
+    Undriven(String),
+    /// The wire depends on its own value.
+    Cycle(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::Unknown(w) => write!(f, "no wire named {}", w),
+            EvalError::Undriven(w) => write!(f, "wire {} has no driver", w),
+            EvalError::Cycle(w) => write!(f, "wire {} depends on itself", w),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+#[derive(Debug, Clone)]
+pub struct Netlist {
+    width: u32,
+    names: Vec<String>,
+    ids: HashMap<String, WireId>,
+    drivers: Vec<Option<Gate>>,
+    memo: Vec<Option<u64>>,
+}
+
+impl Netlist {
+    /// An empty netlist of `width`-bit signals, 1 to 64.
+    pub fn new(width: u32) -> Netlist {
+        assert!((1..=64).contains(&width), "Width must be 1 to 64 bits");
+        Netlist {
+            width,
+            names: vec![],
+            ids: HashMap::new(),
+            drivers: vec![],
+            memo: vec![],
+        }
+    }
+
+    /// Parses one driver per non-empty line. Constants wider than `width`
+    /// are truncated, and a wire may only be driven once.
+    pub fn parse(strs: &[&str], width: u32) -> Result<Netlist, ParseError> {
+        let mut net = Netlist::new(width);
+        for (i, s) in strs.iter().enumerate() {
+            if s.trim().is_empty() {
+                continue;
+            }
+            let err = |message: String| ParseError {
+                line: i + 1,
+                message,
+            };
+            let (lhs, out) = match s.split_once("->") {
+                Some((lhs, out)) => (lhs, out),
+                None => match s.split_once(':') {
+                    Some((out, lhs)) => (lhs, out),
+                    None => return Err(err(format!("Expected a driver, found {:?}", s))),
+                },
+            };
+            let out = out.trim();
+            if out.is_empty() || out.parse::<u64>().is_ok() {
+                return Err(err(format!("Cannot drive {:?}", out)));
+            }
+            let mut operand = |s: &str| match s.parse::<u64>() {
+                Ok(n) => Operand::Const(n & net.mask()),
+                Err(_) => Operand::Wire(net.intern(s)),
+            };
+            let gate = match lhs.split_whitespace().collect::<Vec<_>>()[..] {
+                [a] => Gate::Assign(operand(a)),
+                ["NOT", a] => Gate::Not(operand(a)),
+                [a, op, b] => {
+                    let op = BinOp::parse(op)
+                        .ok_or_else(|| err(format!("Unknown operator {:?}", op)))?;
+                    Gate::Binary(operand(a), op, operand(b))
+                }
+                _ => return Err(err(format!("Cannot parse {:?}", lhs.trim()))),
+            };
+            let out = net.intern(out);
+            if net.drivers[out].replace(gate).is_some() {
+                return Err(err(format!("Wire {} is driven twice", net.names[out])));
+            }
+        }
+        Ok(net)
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.width)
+    }
+
+    fn intern(&mut self, name: &str) -> WireId {
+        if let Some(id) = self.ids.get(name) {
+            return *id;
+        }
+        self.names.push(name.to_string());
+        self.drivers.push(None);
+        self.memo.push(None);
+        self.ids.insert(name.to_string(), self.names.len() - 1);
+        self.names.len() - 1
+    }
+
+    pub fn id(&self, name: &str) -> Option<WireId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: WireId) -> &str {
+        &self.names[id]
+    }
+
+    /// Every wire, in order of first appearance.
+    pub fn wires(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Every driven wire with its gate, in order of first appearance.
+    pub fn gates(&self) -> impl Iterator<Item = (WireId, &Gate)> {
+        self.drivers
+            .iter()
+            .enumerate()
+            .filter_map(|(w, g)| Some((w, g.as_ref()?)))
+    }
+
+    /// Drives `name` with the constant `value` instead of its gate, and
+    /// forgets every value computed so far.
+    pub fn set(&mut self, name: &str, value: u64) {
+        let w = self.intern(name);
+        self.drivers[w] = Some(Gate::Assign(Operand::Const(value & self.mask())));
+        self.memo.fill(None);
+    }
+
+    /// The value of `name`, evaluating only the wires it depends on, each
+    /// at most once.
+    pub fn eval(&mut self, name: &str) -> Result<u64, EvalError> {
+        let w = self
+            .id(name)
+            .ok_or_else(|| EvalError::Unknown(name.to_string()))?;
+        self.eval_id(w)
+    }
+
+    /// Depth-first with an explicit stack, since carry chains run deeper
+    /// than the call stack allows. A wire stays `open` from when its inputs
+    /// are pushed until its value is known; needing an open wire again means
+    /// a cycle.
+    pub fn eval_id(&mut self, root: WireId) -> Result<u64, EvalError> {
+        let mut open = vec![false; self.names.len()];
+        let mut stack = vec![root];
+        while let Some(&w) = stack.last() {
+            if self.memo[w].is_some() {
+                stack.pop();
+                continue;
+            }
+            let gate = self.drivers[w].ok_or_else(|| EvalError::Undriven(self.names[w].clone()))?;
+            let pending: Vec<WireId> = gate.inputs().filter(|i| self.memo[*i].is_none()).collect();
+            if pending.is_empty() {
+                let value = |o: Operand| match o {
+                    Operand::Wire(i) => self.memo[i].unwrap(),
+                    Operand::Const(n) => n,
+                };
+                let v = match gate {
+                    Gate::Assign(a) => value(a),
+                    Gate::Not(a) => !value(a),
+                    Gate::Binary(a, op, b) => op.apply(value(a), value(b)),
+                };
+                self.memo[w] = Some(v & self.mask());
+                open[w] = false;
+                stack.pop();
+                continue;
+            }
+            open[w] = true;
+            for i in pending {
+                if open[i] {
+                    return Err(EvalError::Cycle(self.names[i].clone()));
+                }
+                stack.push(i);
+            }
+        }
+        Ok(self.memo[root].unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example of 2015 day 7.
+    const SIGNALS: &[&str] = &[
+        "123 -> x",
+        "456 -> y",
+        "x AND y -> d",
+        "x OR y -> e",
+        "x LSHIFT 2 -> f",
+        "y RSHIFT 2 -> g",
+        "NOT x -> h",
+        "NOT y -> i",
+    ];
+
+    #[test]
+    fn test_sixteen_bit_signals() {
+        let mut net = Netlist::parse(SIGNALS, 16).unwrap();
+        let expected = [
+            ("d", 72),
+            ("e", 507),
+            ("f", 492),
+            ("g", 114),
+            ("h", 65412),
+            ("i", 65079),
+            ("x", 123),
+            ("y", 456),
+        ];
+        for (wire, v) in expected {
+            assert_eq!(net.eval(wire), Ok(v), "{}", wire);
+        }
+
+        let mut narrow = Netlist::parse(SIGNALS, 8).unwrap();
+        assert_eq!(narrow.eval("y"), Ok(456 & 0xff));
+        assert_eq!(narrow.eval("f"), Ok((123 << 2) & 0xff));
+        assert_eq!(narrow.eval("h"), Ok(!123 & 0xff));
+    }
+
+    #[test]
+    fn test_bool_wires() {
+        let strs = [
+            "x00: 1",
+            "x01: 0",
+            "",
+            "x00 XOR x01 -> z00",
+            "x00 AND x01 -> z01",
+        ];
+        let mut net = Netlist::parse(&strs, 1).unwrap();
+        assert_eq!(net.eval("z00"), Ok(1));
+        assert_eq!(net.eval("z01"), Ok(0));
+        assert_eq!(net.gates().count(), 4);
+    }
+
+    #[test]
+    fn test_override() {
+        let mut net = Netlist::parse(&["e -> a", "x OR y -> e", "1 -> x", "2 -> y"], 16).unwrap();
+        let a = net.eval("a").unwrap();
+        assert_eq!(a, 3);
+        net.set("y", a << 2);
+        assert_eq!(net.eval("a"), Ok(13));
+        net.set("b", 7);
+        assert_eq!(net.eval("b"), Ok(7));
+    }
+
+    #[test]
+    fn test_deep_chain() {
+        let lines: Vec<String> = (0..100_000)
+            .map(|i| format!("w{} -> w{}", i + 1, i))
+            .chain(["5 -> w100000".to_string()])
+            .collect();
+        let strs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let mut net = Netlist::parse(&strs, 16).unwrap();
+        assert_eq!(net.eval("w0"), Ok(5));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut net = Netlist::parse(&["b AND c -> a", "a -> b", "1 -> c", "d -> e"], 16).unwrap();
+        assert_eq!(net.eval("a"), Err(EvalError::Cycle("a".into())));
+        assert_eq!(net.eval("c"), Ok(1));
+        assert_eq!(net.eval("e"), Err(EvalError::Undriven("d".into())));
+        assert_eq!(net.eval("q"), Err(EvalError::Unknown("q".into())));
+
+        let err = |strs: &[&str]| Netlist::parse(strs, 16).unwrap_err().to_string();
+        assert_eq!(
+            err(&["1 -> a", "", "2 -> a"]),
+            "line 3: Wire a is driven twice"
+        );
+        assert_eq!(err(&["a NAND b -> c"]), "line 1: Unknown operator \"NAND\"");
+        assert_eq!(err(&["a -> 3"]), "line 1: Cannot drive \"3\"");
+        assert_eq!(
+            err(&["just a wire"]),
+            "line 1: Expected a driver, found \"just a wire\""
+        );
+    }
+}
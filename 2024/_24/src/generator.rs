@@ -0,0 +1,156 @@
+//! Crossed wires: a ripple-carry adder with some gate outputs swapped.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc_runner::rng::Rng;
+
+struct Gate {
+    a: String,
+    op: &'static str,
+    b: String,
+    out: String,
+}
+
+/// Whether every wire settles, i.e. no swap closed a loop.
+fn settles(inputs: &[String], gates: &[Gate]) -> bool {
+    let mut known: HashSet<&str> = inputs.iter().map(String::as_str).collect();
+    let mut pending: Vec<&Gate> = gates.iter().collect();
+    while !pending.is_empty() {
+        let before = pending.len();
+        pending.retain(|g| {
+            let ready = known.contains(g.a.as_str()) && known.contains(g.b.as_str());
+            if ready {
+                known.insert(&g.out);
+            }
+            !ready
+        });
+        if pending.len() == before {
+            return false;
+        }
+    }
+    true
+}
+
+/// Adds two `bits`-bit numbers with the usual five gates per bit, then
+/// swaps the outputs of `swaps` disjoint pairs of gates.
+pub fn adder(bits: usize, swaps: usize, r: &mut Rng) -> String {
+    let mut used: HashSet<String> = HashSet::new();
+    let mut wire = |r: &mut Rng| loop {
+        let name = format!(
+            "{}{}",
+            r.string("abcdefghijklmnopqrstuvw", 1),
+            r.string("abcdefghijklmnopqrstuvwxyz", 2)
+        );
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+    let gate = |a: &str, op, b: &str, out: String| Gate {
+        a: a.to_string(),
+        op,
+        b: b.to_string(),
+        out,
+    };
+
+    let mut gates = vec![];
+    let mut carry = String::new();
+    for i in 0..bits {
+        let (x, y, z) = (
+            format!("x{:02}", i),
+            format!("y{:02}", i),
+            format!("z{:02}", i),
+        );
+        if i == 0 {
+            carry = wire(r);
+            gates.push(gate(&x, "XOR", &y, z));
+            gates.push(gate(&x, "AND", &y, carry.clone()));
+            continue;
+        }
+        let (sum, both, through) = (wire(r), wire(r), wire(r));
+        let next = match i + 1 == bits {
+            true => format!("z{:02}", bits),
+            false => wire(r),
+        };
+        gates.push(gate(&x, "XOR", &y, sum.clone()));
+        gates.push(gate(&x, "AND", &y, both.clone()));
+        gates.push(gate(&sum, "XOR", &carry, z));
+        gates.push(gate(&sum, "AND", &carry, through.clone()));
+        gates.push(gate(&both, "OR", &through, next.clone()));
+        carry = next;
+    }
+
+    let inputs: Vec<String> = (0..bits)
+        .flat_map(|i| [format!("x{:02}", i), format!("y{:02}", i)])
+        .collect();
+    let mut swapped: HashSet<usize> = HashSet::new();
+    let mut tries = 0;
+    while swapped.len() < swaps * 2 && tries < 1000 {
+        tries += 1;
+        let (i, j) = (r.below(gates.len()), r.below(gates.len()));
+        if i == j || swapped.contains(&i) || swapped.contains(&j) {
+            continue;
+        }
+        let out = std::mem::take(&mut gates[i].out);
+        gates[i].out = std::mem::replace(&mut gates[j].out, out);
+        if settles(&inputs, &gates) {
+            swapped.extend([i, j]);
+        } else {
+            let out = std::mem::take(&mut gates[i].out);
+            gates[i].out = std::mem::replace(&mut gates[j].out, out);
+        }
+    }
+
+    let values: HashMap<&String, bool> = inputs.iter().map(|w| (w, r.chance(0.5))).collect();
+    let mut lines: Vec<String> = inputs
+        .iter()
+        .map(|w| format!("{}: {}", w, values[w] as u8))
+        .collect();
+    lines.sort();
+    lines.push(String::new());
+    r.shuffle(&mut gates);
+    for g in gates.iter() {
+        let (a, b) = match r.chance(0.5) {
+            true => (&g.a, &g.b),
+            false => (&g.b, &g.a),
+        };
+        lines.push(format!("{} {} {} -> {}", a, g.op, b, g.out));
+    }
+    lines.join("\n")
+}
+
+/// A `size`-bit adder (2 to 63 bits) with four pairs of outputs swapped.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    adder(size.clamp(2, 63), 4, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::solve_part1;
+
+    fn operand(lines: &[&str], wire: char) -> u64 {
+        lines
+            .iter()
+            .filter(|l| l.starts_with(wire) && l.contains(':'))
+            .enumerate()
+            .map(|(i, l)| (l.ends_with('1') as u64) << i)
+            .sum()
+    }
+
+    #[test]
+    fn test_generated_adder() {
+        let mut r = Rng::new(24);
+        let input = adder(20, 0, &mut r);
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 40 + 1 + 2 + 19 * 5);
+        assert_eq!(
+            solve_part1(&lines),
+            operand(&lines, 'x') + operand(&lines, 'y')
+        );
+
+        let input = generate(45, &mut r);
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 90 + 1 + 2 + 44 * 5);
+        solve_part1(&lines);
+    }
+}
@@ -1,211 +1,89 @@
-use core::panic;
-use std::collections::{BTreeMap, LinkedList};
+use crate::circuit::{Gate, Netlist, Operand};
 
-pub fn solve_part1(strs: &[&str]) -> u64 {
-    let mut r = parse_input(strs);
-    r.process_wiring();
-    r.get_number()
+fn parse(strs: &[&str]) -> Netlist {
+    Netlist::parse(strs, 1).unwrap_or_else(|e| panic!("Invalid wiring: {}", e))
 }
 
-pub fn solve_part2(strs: &[&str]) -> usize {
-    let r = parse_input(strs);
-    println!("graph");
-
-    for (i, g) in r.gates.iter().enumerate() {
-        println!("  {} --> {:?}{}", g.a, g.op, i);
-        println!("  {} --> {:?}{}", g.b, g.op, i);
-        println!("  {:?}{} --> {}", g.op, i, g.out);
-    }
-    0
-}
-
-#[derive(Debug)]
-enum GateOp {
-    And,
-    Or,
-    Xor,
-}
-
-#[derive(Debug)]
-struct Gate {
-    op: GateOp,
-    a: String,
-    b: String,
-    out: String,
+/// The number on the `z` wires, `z00` being the lowest bit.
+fn z_number(net: &mut Netlist) -> u64 {
+    let mut zs: Vec<String> = net
+        .wires()
+        .filter(|w| w.starts_with('z'))
+        .map(str::to_string)
+        .collect();
+    zs.sort();
+    zs.iter().rev().fold(0, |acc, z| {
+        let bit = net.eval(z).unwrap_or_else(|e| panic!("{}", e));
+        acc << 1 | bit
+    })
 }
 
-#[derive(Debug)]
-struct Wiring {
-    inputs: BTreeMap<String, bool>,
-    gates: LinkedList<Gate>,
+pub fn solve_part1(strs: &[&str]) -> u64 {
+    z_number(&mut parse(strs))
 }
 
-fn parse_input(strs: &[&str]) -> Wiring {
-    let mut s = strs.split(|x| x.is_empty());
-
-    let inputs: BTreeMap<String, bool> = s
-        .next()
-        .unwrap()
-        .iter()
-        .map(|s| {
-            let mut m = s.split(":").map(str::trim);
-            let name = m.next().unwrap().to_string();
-            let state = match m.next().unwrap() {
-                "1" => true,
-                "0" => false,
-                _ => panic!("not 1 or 0"),
-            };
-            (name, state)
-        })
-        .collect();
-
-    let gates: LinkedList<Gate> = s
-        .next()
-        .unwrap()
-        .iter()
-        .map(|s| {
-            let mut m = s.split_whitespace();
-            let a = m.next().unwrap().to_string();
-            let op = m.next().unwrap();
-            let b = m.next().unwrap().to_string();
-            m.next();
-            let out = m.next().unwrap().to_string();
-
-            match op {
-                "AND" => Gate {
-                    a,
-                    b,
-                    out,
-                    op: GateOp::And,
-                },
-                "OR" => Gate {
-                    a,
-                    b,
-                    out,
-                    op: GateOp::Or,
-                },
-                "XOR" => Gate {
-                    a,
-                    b,
-                    out,
-                    op: GateOp::Xor,
-                },
-                _ => panic!("Unknown gate"),
-            }
+/// Bits of the adder where a lone one on `x` or `y`, with every other input
+/// zero, fails to come out on the same bit of `z`: the neighbourhood of a
+/// swapped wire.
+pub fn faulty_bits(strs: &[&str]) -> Vec<usize> {
+    let mut net = parse(strs);
+    let bits = net.wires().filter(|w| w.starts_with('x')).count();
+    (0..bits)
+        .filter(|i| {
+            ['x', 'y'].iter().any(|side| {
+                for j in 0..bits {
+                    net.set(&format!("x{:02}", j), 0);
+                    net.set(&format!("y{:02}", j), 0);
+                }
+                net.set(&format!("{}{:02}", side, i), 1);
+                z_number(&mut net) != 1 << i
+            })
         })
-        .collect();
-
-    Wiring { inputs, gates }
+        .collect()
 }
 
-impl Wiring {
-    fn process_wiring(&mut self) {
-        while let Some(gate) = self.gates.pop_back() {
-            let a = self.inputs.get(&gate.a);
-            let b = self.inputs.get(&gate.b);
-            if let (Some(a), Some(b)) = (a, b) {
-                let out = match gate.op {
-                    GateOp::And => *a && *b,
-                    GateOp::Or => *a || *b,
-                    GateOp::Xor => *a ^ *b,
-                };
-                self.inputs.insert(gate.out, out);
-            } else {
-                self.gates.push_front(gate);
-            }
-        }
-    }
+pub fn solve_part2(strs: &[&str]) -> usize {
+    let net = parse(strs);
+    println!("graph");
 
-    fn get_number(&self) -> u64 {
-        let mut c: u64 = 0;
-        let z_vals: Vec<u64> = self
-            .inputs
-            .iter()
-            .filter_map(|(k, v)| {
-                if k.starts_with("z") {
-                    Some(*v as u64)
-                } else {
-                    None
-                }
-            })
-            .collect();
-        for (i, v) in z_vals.into_iter().enumerate() {
-            let i: u64 = i.try_into().unwrap();
-            c += v * (2_u64.pow(i.try_into().unwrap()));
+    let name = |o: &Operand| match o {
+        Operand::Wire(w) => net.name(*w).to_string(),
+        Operand::Const(n) => n.to_string(),
+    };
+    for (i, (out, gate)) in net.gates().enumerate() {
+        if let Gate::Binary(a, op, b) = gate {
+            println!("  {} --> {}{}", name(a), op.name(), i);
+            println!("  {} --> {}{}", name(b), op.name(), i);
+            println!("  {}{} --> {}", op.name(), i, net.name(out));
         }
-        c
     }
+    0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generator::adder;
+    use aoc_fixture::{fixture, puzzle_tests};
+    use aoc_runner::rng::Rng;
 
-    const EXAMPLE: &str = "
-        x00: 1
-        x01: 0
-        x02: 1
-        x03: 1
-        x04: 0
-        y00: 1
-        y01: 1
-        y02: 1
-        y03: 1
-        y04: 1
-        
-        ntg XOR fgs -> mjb
-        y02 OR x01 -> tnw
-        kwq OR kpj -> z05
-        x00 OR x03 -> fst
-        tgd XOR rvg -> z01
-        vdt OR tnw -> bfw
-        bfw AND frj -> z10
-        ffh OR nrd -> bqk
-        y00 AND y03 -> djm
-        y03 OR y00 -> psh
-        bqk OR frj -> z08
-        tnw OR fst -> frj
-        gnj AND tgd -> z11
-        bfw XOR mjb -> z00
-        x03 OR x00 -> vdt
-        gnj AND wpb -> z02
-        x04 AND y00 -> kjc
-        djm OR pbm -> qhw
-        nrd AND vdt -> hwm
-        kjc AND fst -> rvg
-        y04 OR y02 -> fgs
-        y01 AND x02 -> pbm
-        ntg OR kjc -> kwq
-        psh XOR fgs -> tgd
-        qhw XOR tgd -> z09
-        pbm OR djm -> kpj
-        x03 XOR y03 -> ffh
-        x00 XOR y04 -> ntg
-        bfw OR bqk -> z06
-        nrd XOR fgs -> wpb
-        frj XOR qhw -> z04
-        bqk OR frj -> z07
-        y03 OR x01 -> nrd
-        hwm AND bqk -> z03
-        tgd XOR rvg -> z12
-        tnw OR pbm -> gnj";
+    #[test]
+    fn test_parsing() {
+        let f = fixture!("example");
 
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.strip_prefix("\n")
-            .unwrap()
-            .lines()
-            .map(str::trim)
-            .collect()
+        let mut net = parse(&f.lines());
+        assert_eq!(net.gates().count(), 46);
+        f.assert_part1(z_number(&mut net));
     }
 
     #[test]
-    fn test_parsing() {
-        let input = read_input(EXAMPLE);
-
-        let mut r = parse_input(&input);
-        r.process_wiring();
-        dbg!(r.inputs.len());
-        let res = r.get_number();
-        dbg!(res);
+    fn test_faulty_bits() {
+        let mut r = Rng::new(24);
+        let sound = adder(12, 0, &mut r);
+        assert!(faulty_bits(&sound.lines().collect::<Vec<_>>()).is_empty());
+        let crossed = adder(12, 2, &mut r);
+        assert!(!faulty_bits(&crossed.lines().collect::<Vec<_>>()).is_empty());
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
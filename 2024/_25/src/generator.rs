@@ -0,0 +1,45 @@
+//! Lock and key schematics.
+
+use aoc_runner::rng::Rng;
+
+/// One 5x7 block: pins hang from the top row of a lock and rise from the
+/// bottom row of a key.
+fn schematic(lock: bool, r: &mut Rng) -> String {
+    let heights: Vec<usize> = (0..5).map(|_| r.range(0, 5)).collect();
+    let rows: Vec<String> = (0..7)
+        .map(|y| {
+            let from_base = if lock { y } else { 6 - y };
+            heights
+                .iter()
+                .map(|h| if from_base <= *h { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+    rows.join("\n")
+}
+
+/// `size` schematics, about half of them locks.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let blocks: Vec<String> = (0..size)
+        .map(|_| {
+            let lock = r.chance(0.5);
+            schematic(lock, r)
+        })
+        .collect();
+    blocks.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{fitting_lines, solve_part1};
+
+    #[test]
+    fn test_generated_schematics() {
+        let input = generate(100, &mut Rng::new(25));
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(lines.len(), 100 * 8 - 1);
+        assert!(solve_part1(&lines) > 0);
+        assert_eq!(fitting_lines(&lines).len(), solve_part1(&lines));
+    }
+}
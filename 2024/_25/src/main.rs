@@ -1,29 +1,26 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::io;
 
+use aoc_runner::Day;
+
+mod generator;
+mod schematic;
 mod solution;
 
-use solution::{solve_part1, solve_part2};
+use solution::{fitting_lines, solve_part1, solve_part2};
 
 fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v: Vec<&str> = v.iter().map(String::as_str).collect();
-    let res = solve_part1(&v);
-
-    println!("Part1:");
-    println!("{}", res);
-
-    let res = solve_part2(&v);
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    day.part(1, |i| solve_part1(&i.lines()));
+    day.part(2, |i| solve_part2(&i.lines()));
 
-    println!("Part2:");
-    println!("{:?}", res);
+    day.extras(|i| {
+        for (key, lock) in fitting_lines(&i.lines()).iter().take(10) {
+            println!("Key at line {} fits lock at line {}", key, lock);
+        }
+    });
 
-    Ok(())
+    day.finish()
 }
@@ -0,0 +1,242 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Lock,
+    Key,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line in the input.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    pub kind: Kind,
+    /// 1-based line of the block's first row.
+    pub line: usize,
+    pub heights: Vec<usize>,
+    /// Each column owns `space` bits; a lock sets the low `height` bits of its
+    /// column and a key the high ones, so a pair fits iff the masks are disjoint.
+    mask: u64,
+}
+
+impl Schematic {
+    pub fn fits(&self, other: &Schematic) -> bool {
+        self.kind != other.kind && self.mask & other.mask == 0
+    }
+}
+
+#[derive(Debug)]
+pub struct Schematics {
+    pub locks: Vec<Schematic>,
+    pub keys: Vec<Schematic>,
+}
+
+impl Schematics {
+    /// Parses blank-line separated blocks. Every block must have the size of
+    /// the first one, consist of `#` and `.`, have exactly one of its top and
+    /// bottom rows filled, and grow each pin unbroken from that row.
+    pub fn parse(strs: &[&str]) -> Result<Schematics, ParseError> {
+        let err = |line: usize, message: String| ParseError { line, message };
+
+        let mut blocks: Vec<(usize, Vec<&str>)> = vec![];
+        let mut cur: Option<(usize, Vec<&str>)> = None;
+        for (i, s) in strs.iter().enumerate() {
+            if s.is_empty() {
+                blocks.extend(cur.take());
+            } else {
+                cur.get_or_insert_with(|| (i + 1, vec![])).1.push(s);
+            }
+        }
+        blocks.extend(cur);
+
+        let (first_line, first) = blocks
+            .first()
+            .ok_or_else(|| err(1, "No schematics".into()))?;
+        let height = first.len();
+        let width = first[0].len();
+        if height < 3 {
+            return Err(err(
+                *first_line,
+                format!("Schematic has only {} rows", height),
+            ));
+        }
+        let space = height - 2;
+        if width == 0 || width * space > 64 {
+            return Err(err(
+                *first_line,
+                format!("Schematic of {}x{} does not fit a mask", width, height),
+            ));
+        }
+
+        let mut res = Schematics {
+            locks: vec![],
+            keys: vec![],
+        };
+        for (line, rows) in blocks.iter() {
+            if rows.len() != height {
+                return Err(err(
+                    *line,
+                    format!("Expected {} rows, found {}", height, rows.len()),
+                ));
+            }
+            for (i, row) in rows.iter().enumerate() {
+                if row.len() != width {
+                    return Err(err(
+                        line + i,
+                        format!("Expected {} columns, found {}", width, row.len()),
+                    ));
+                }
+                if let Some(ch) = row.chars().find(|ch| *ch != '#' && *ch != '.') {
+                    return Err(err(line + i, format!("Unexpected {:?}", ch)));
+                }
+            }
+
+            let filled = |row: &str| row.bytes().all(|b| b == b'#');
+            let kind = match (filled(rows[0]), filled(rows[height - 1])) {
+                (true, false) => Kind::Lock,
+                (false, true) => Kind::Key,
+                _ => {
+                    return Err(err(
+                        *line,
+                        "Exactly one of the top and bottom rows must be filled".into(),
+                    ))
+                }
+            };
+
+            let mut heights = vec![];
+            let mut mask = 0;
+            for x in 0..width {
+                let col = |y: usize| rows[y].as_bytes()[x] == b'#';
+                let h = (1..height - 1)
+                    .take_while(|y| match kind {
+                        Kind::Lock => col(*y),
+                        Kind::Key => col(height - 1 - y),
+                    })
+                    .count();
+                // Rows past the pin, ignoring the filled base row.
+                let gap = (h + 1..height - 1).find(|y| match kind {
+                    Kind::Lock => col(*y),
+                    Kind::Key => col(height - 1 - y),
+                });
+                if let Some(y) = gap {
+                    let row = match kind {
+                        Kind::Lock => y,
+                        Kind::Key => height - 1 - y,
+                    };
+                    return Err(err(line + row, format!("Broken pin in column {}", x)));
+                }
+                // A pin may fill a whole 64-bit column, so no `1 << h`.
+                let bits = match h {
+                    0 => 0,
+                    h => {
+                        let low = u64::MAX >> (64 - h);
+                        match kind {
+                            Kind::Lock => low,
+                            Kind::Key => low << (space - h),
+                        }
+                    }
+                };
+                mask |= bits << (x * space);
+                heights.push(h);
+            }
+
+            let s = Schematic {
+                kind,
+                line: *line,
+                heights,
+                mask,
+            };
+            match kind {
+                Kind::Lock => res.locks.push(s),
+                Kind::Key => res.keys.push(s),
+            }
+        }
+        Ok(res)
+    }
+
+    /// Every fitting pair as `(key, lock)` indices into `keys` and `locks`.
+    pub fn fitting_pairs(&self) -> Vec<(usize, usize)> {
+        self.keys
+            .iter()
+            .enumerate()
+            .flat_map(|(k, key)| {
+                self.locks
+                    .iter()
+                    .enumerate()
+                    .filter(move |(_, lock)| key.fits(lock))
+                    .map(move |(l, _)| (k, l))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fits() {
+        let s = Schematics::parse(&["###", "#..", "...", "...", "", "...", "..#", ".##", "###"])
+            .unwrap();
+        assert_eq!(s.locks[0].heights, vec![1, 0, 0]);
+        assert_eq!(s.keys[0].heights, vec![0, 1, 2]);
+        assert_eq!(s.keys[0].line, 6);
+        assert_eq!(s.fitting_pairs(), vec![(0, 0)]);
+
+        let s = Schematics::parse(&["###", "###", "...", "", "...", "#..", "###"]).unwrap();
+        assert!(s.fitting_pairs().is_empty());
+    }
+
+    #[test]
+    fn test_full_mask() {
+        // One column of 64 pin rows fills the mask exactly.
+        let lock = ["#"; 65].into_iter().chain(["."]);
+        let key = |h: usize| {
+            let mut rows = vec!["."; 65 - h];
+            rows.extend(vec!["#"; h + 1]);
+            rows
+        };
+        let mut strs: Vec<&str> = lock.collect();
+        for h in [0, 1] {
+            strs.push("");
+            strs.extend(key(h));
+        }
+        let s = Schematics::parse(&strs).unwrap();
+        assert_eq!(s.locks[0].heights, vec![64]);
+        assert_eq!(s.keys[0].heights, vec![0]);
+        assert_eq!(s.keys[1].heights, vec![1]);
+        assert_eq!(s.fitting_pairs(), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let e = |strs: &[&str]| Schematics::parse(strs).unwrap_err().line;
+        assert_eq!(e(&[]), 1);
+        assert_eq!(e(&["###", "...", "...", "", "###", "..."]), 5);
+        assert_eq!(e(&["###", "...", "...", "", "###", "..", "..."]), 6);
+        assert_eq!(e(&["###", ".x.", "..."]), 2);
+        assert_eq!(e(&["###", "...", "###"]), 1);
+        assert_eq!(e(&["...", "...", "..."]), 1);
+        assert_eq!(e(&["", "###", "...", "#..", "..."]), 4);
+        assert_eq!(e(&["...", "#..", "...", "###"]), 2);
+        assert_eq!(
+            Schematics::parse(&["###", ".x.", "..."])
+                .unwrap_err()
+                .to_string(),
+            "line 2: Unexpected 'x'"
+        );
+    }
+}
@@ -1,132 +1,39 @@
-pub fn solve_part1(strs: &[&str]) -> usize {
-    let p = parse_input(strs);
-    let mut c = 0;
-    for key in p.keys {
-        for lock in p.locks.iter() {
-            let fits = key.iter().zip(lock.iter()).all(|(k_p, l_p)| k_p + l_p <= 5);
-            if fits {
-                c += 1;
-            }
-        }
-    }
-    c
-}
+use crate::schematic::Schematics;
 
-pub fn solve_part2(strs: &[&str]) -> usize {
-    0
+fn parse(strs: &[&str]) -> Schematics {
+    Schematics::parse(strs).unwrap_or_else(|e| panic!("Invalid schematic: {}", e))
 }
 
-#[derive(Debug)]
-struct Obj {
-    pins: Vec<usize>,
-    is_lock: bool,
-}
-
-fn parse_grid(strs: &[&str]) -> Obj {
-    let first = strs.first().unwrap().chars().all(|c| c == '#');
-    let n = strs[0].len();
-    let mut pins = vec![];
-    for i in 0..n {
-        let mut c = 0;
-        for line in strs {
-            if line.chars().nth(i).unwrap() == '#' {
-                c += 1;
-            }
-        }
-        pins.push(c - 1);
-    }
-    if first {
-        Obj {
-            is_lock: true,
-            pins,
-        }
-    } else {
-        Obj {
-            is_lock: false,
-            pins,
-        }
-    }
+pub fn solve_part1(strs: &[&str]) -> usize {
+    parse(strs).fitting_pairs().len()
 }
 
-#[derive(Debug)]
-struct Parsed {
-    keys: Vec<Vec<usize>>,
-    locks: Vec<Vec<usize>>,
+/// Every fitting pair, as the starting lines of the key and the lock.
+pub fn fitting_lines(strs: &[&str]) -> Vec<(usize, usize)> {
+    let s = parse(strs);
+    s.fitting_pairs()
+        .into_iter()
+        .map(|(k, l)| (s.keys[k].line, s.locks[l].line))
+        .collect()
 }
 
-fn parse_input(strs: &[&str]) -> Parsed {
-    let mut p = Parsed {
-        keys: vec![],
-        locks: vec![],
-    };
-    strs.split(|x| x.is_empty()).for_each(|x| {
-        let obj = parse_grid(x);
-        if obj.is_lock {
-            p.locks.push(obj.pins);
-        } else {
-            p.keys.push(obj.pins);
-        }
-    });
-    p
+/// The last day has no second puzzle.
+pub fn solve_part2(_strs: &[&str]) -> usize {
+    0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.strip_prefix("\n")
-            .unwrap()
-            .lines()
-            .map(str::trim)
-            .collect()
-    }
-
-    const EXAMPLE: &str = "
-        #####
-        .####
-        .####
-        .####
-        .#.#.
-        .#...
-        .....
-        
-        #####
-        ##.##
-        .#.##
-        ...##
-        ...#.
-        ...#.
-        .....
-        
-        .....
-        #....
-        #....
-        #...#
-        #.#.#
-        #.###
-        #####
-        
-        .....
-        .....
-        #.#..
-        ###..
-        ###.#
-        ###.#
-        #####
-        
-        .....
-        .....
-        .....
-        #....
-        #.#..
-        #.#.#
-        #####";
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_solved() {
-        let input = read_input(EXAMPLE);
-        let r = solve_part1(&input);
-        dbg!(r);
+        let f = fixture!("example");
+        let input = f.lines();
+        f.assert_part1(solve_part1(&input));
+        assert_eq!(fitting_lines(&input), vec![(25, 9), (33, 1), (33, 9)]);
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,46 @@
+//! Corrupted memory: `mul` instructions, some with a character inserted or
+//! replaced, between `do()`, `don't()` and noise.
+
+use aoc_runner::rng::Rng;
+
+/// `size` instructions and pieces of noise, on lines of about a hundred.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let noise = ["do()", "don't()", "mul", "(", ")", ",", "x", " "];
+    let mut s = String::new();
+    for _ in 0..size {
+        if r.chance(0.01) {
+            s.push('\n');
+        }
+        if r.chance(0.4) {
+            let piece = r.pick(&noise);
+            s.push_str(piece);
+            continue;
+        }
+        let (n, m) = (r.range(1, 4), r.range(1, 4));
+        let (a, b) = (r.string("0123456789", n), r.string("0123456789", m));
+        let mut ins: Vec<char> = format!("mul({},{})", a, b).chars().collect();
+        if r.chance(0.5) {
+            let i = r.below(ins.len());
+            let c = *r.pick(&['+', '-', ' ', ',', ')', '1']);
+            match r.chance(0.5) {
+                true => ins.insert(i, c),
+                false => ins[i] = c,
+            }
+        }
+        s.extend(ins);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_memory() {
+        let input = generate(1000, &mut Rng::new(3));
+        assert!(input.lines().count() > 1);
+        assert!(solve_part1(&input) > solve_part2(&input));
+    }
+}
@@ -1,5 +1,8 @@
+use aoc_runner::diff::Variant;
 use regex::Regex;
 
+pub mod generator;
+
 fn parse_regex(s: &str) -> Vec<(u32, u32)> {
     let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
 
@@ -14,6 +17,14 @@ fn parse_regex(s: &str) -> Vec<(u32, u32)> {
     finds
 }
 
+/// One to three digits, without the sign `parse` would accept.
+fn operand(s: &str) -> Option<u32> {
+    match s.bytes().all(|b| b.is_ascii_digit()) {
+        true => s.parse().ok(),
+        false => None,
+    }
+}
+
 fn parse_program(s: &str) -> Vec<(u32, u32)> {
     let mut s = s;
 
@@ -34,8 +45,8 @@ fn parse_program(s: &str) -> Vec<(u32, u32)> {
 
             //println!("possible number0: {}", n0);
 
-            let n0: u32 = match n0.parse() {
-                Ok(n) => n,
+            let n0: u32 = match operand(n0) {
+                Some(n) => n,
                 _ => continue,
             };
 
@@ -54,8 +65,8 @@ fn parse_program(s: &str) -> Vec<(u32, u32)> {
 
                 //println!("possible number1: {}", n1);
 
-                let n1: u32 = match n1.parse() {
-                    Ok(n) => n,
+                let n1: u32 = match operand(n1) {
+                    Some(n) => n,
                     _ => continue,
                 };
 
@@ -75,13 +86,22 @@ fn parse_program(s: &str) -> Vec<(u32, u32)> {
     res
 }
 
-fn count_program(s: &str) -> u32 {
-    let p = parse_regex(s);
+type Parser = fn(&str) -> Vec<(u32, u32)>;
+
+/// Ways of finding the `mul` instructions, for `--impl`.
+pub const PARSERS: &[Variant<Parser>] = &[("regex", parse_regex), ("scanner", parse_program)];
+
+fn count_program(parse: Parser, s: &str) -> u32 {
+    let p = parse(s);
     p.iter().map(|n| n.0 * n.1).sum()
 }
 
+pub fn solve_part1_with(parse: Parser, str: &str) -> u32 {
+    count_program(parse, str)
+}
+
 pub fn solve_part1(str: &str) -> u32 {
-    count_program(str)
+    solve_part1_with(parse_regex, str)
 }
 
 fn get_enabled_blocks(s: &str) -> Vec<&str> {
@@ -128,20 +148,26 @@ fn get_enabled_blocks(s: &str) -> Vec<&str> {
     res
 }
 
-fn solve_one(str: &str) -> u32 {
+fn solve_one(parse: Parser, str: &str) -> u32 {
     get_enabled_blocks(str)
         .iter()
-        .map(|x| count_program(x))
+        .map(|x| count_program(parse, x))
         .sum()
 }
 
+pub fn solve_part2_with(parse: Parser, str: &str) -> u32 {
+    solve_one(parse, str)
+}
+
 pub fn solve_part2(str: &str) -> u32 {
-    solve_one(str)
+    solve_part2_with(parse_regex, str)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_fixture::{fixture, puzzle_tests};
+    use aoc_runner::diff::Check;
 
     #[test]
     fn test_parse_program() {
@@ -154,10 +180,14 @@ mod tests {
 
     #[test]
     fn test_name() {
-        let case: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-        let expected: u32 = 161;
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(f.input()));
+    }
 
-        assert_eq!(solve_part1(case), expected);
+    #[test]
+    fn test_part2() {
+        let f = fixture!("example2");
+        f.assert_part2(solve_part2(f.input()));
     }
 
     #[test]
@@ -210,7 +240,7 @@ mod tests {
 
         let expected: Vec<&str> = vec!["do()_mul(5,5)+mul(32,64]", "?mul(8,5))do()mul(11,8)"];
 
-        dbg!(solve_one(case));
+        dbg!(solve_one(parse_regex, case));
 
         assert_eq!(get_enabled_blocks(case), expected);
     }
@@ -221,7 +251,7 @@ mod tests {
 
         let expected: Vec<&str> = vec!["aaa", "bbb"];
 
-        dbg!(solve_one(case));
+        dbg!(solve_one(parse_regex, case));
 
         assert_eq!(get_enabled_blocks(case), expected);
     }
@@ -232,7 +262,7 @@ mod tests {
 
         let expected: Vec<&str> = vec!["aaado()"];
 
-        dbg!(solve_one(case));
+        dbg!(solve_one(parse_regex, case));
 
         assert_eq!(get_enabled_blocks(case), expected);
     }
@@ -279,4 +309,14 @@ mod tests {
 
         println!("RESULT: {:#?}", get_enabled_blocks(case));
     }
+
+    #[test]
+    fn test_parsers_agree() {
+        Check::new(PARSERS).cases(500).assert_agree(
+            |parse, s| solve_part2_with(*parse, s),
+            |r| generator::generate(r.range(0, 6), r),
+        );
+    }
+
+    puzzle_tests!(|f| solve_part1(f.input()), |f| solve_part2(f.input()));
 }
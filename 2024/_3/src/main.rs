@@ -1,23 +1,17 @@
-use std::{
-    fs,
-    io::{self},
-    path::Path,
-};
+use std::io;
 
-use _2024_3::{solve_part1, solve_part2};
+use aoc_runner::Day;
 
-fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let v = fs::read_to_string(input_path).unwrap();
-
-    println!("Part1:");
-    let res = solve_part1(v.as_str());
-    println!("{}", res);
+use _2024_3::{generator, solve_part1_with, solve_part2_with, PARSERS};
 
-    println!("Part2:");
-    let res = solve_part2(v.as_str());
-    println!("{}", res);
+fn main() -> io::Result<()> {
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    let parse = day.variant(PARSERS);
+    day.part(1, move |i| solve_part1_with(parse, i.text()));
+    day.part(2, move |i| solve_part2_with(parse, i.text()));
 
-    Ok(())
+    day.finish()
 }
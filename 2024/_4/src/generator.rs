@@ -0,0 +1,25 @@
+//! Word searches over the letters of `XMAS`.
+
+use aoc_runner::rng::Rng;
+
+/// A `size` by `size` grid.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (0..size)
+        .map(|_| r.string("XMAS", size))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_grid() {
+        let input = generate(40, &mut Rng::new(4));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(solve_part1(&lines) > 0);
+        assert!(solve_part2(&lines) > 0);
+    }
+}
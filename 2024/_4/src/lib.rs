@@ -1,3 +1,5 @@
+pub mod generator;
+
 fn count_line(line: &str) -> u32 {
     let mut count = 0;
     if line.eq("XMAS") {
@@ -123,64 +125,21 @@ pub fn solve_part2(strs: &[&str]) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = "
-        MMMSXXMASM
-        MSAMXMSMSA
-        AMXSXMAAMM
-        MSAMASMSMX
-        XMASAMXAMM
-        XXAMMXXAMA
-        SMSMSASXSS
-        SAXAMASAAA
-        MAMMMXMMMM
-        MXMXAXMASX
-    ";
-
-    const INPUT2: &str = "
-       .M.S......
-       ..A..MSMS.
-       .M.S.MAA..
-       ..A.ASMSM.
-       .M.S.M....
-       ..........
-       S.S.S.S.S.
-       .A.A.A.A..
-       M.M.M.M.M.
-       ..........
-    ";
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_part1() {
-        let input: Vec<&str> = INPUT
-            .lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect();
-
-        println!("{:?}", input);
-
-        let res = solve_part1(&input);
-
-        println!("{}", res);
-
-        assert_eq!(res, 18);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_part2() {
-        let input: Vec<&str> = INPUT2
-            .lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect();
-
-        println!("{:?}", input);
-
-        let res = solve_part2(&input);
-
-        println!("{}", res);
-
-        assert_eq!(res, 9);
+        let f = fixture!("xmas");
+        f.assert_part2(solve_part2(&f.lines()));
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,50 @@
+//! Page ordering rules and updates. The rules order every pair of pages, as
+//! the puzzle's do for the pages that appear together.
+
+use aoc_runner::rng::Rng;
+
+/// Rules over up to 89 two-digit pages, then `size` updates of odd length,
+/// about half of them in order.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let mut pages: Vec<usize> = (10..=99).collect();
+    r.shuffle(&mut pages);
+    pages.truncate(size.clamp(5, 89));
+
+    let mut rules = vec![];
+    for (i, a) in pages.iter().enumerate() {
+        for b in pages[i + 1..].iter() {
+            rules.push(format!("{}|{}", a, b));
+        }
+    }
+    r.shuffle(&mut rules);
+
+    let updates: Vec<String> = (0..size)
+        .map(|_| {
+            let mut idx: Vec<usize> = (0..pages.len()).collect();
+            r.shuffle(&mut idx);
+            idx.truncate(r.range(1, pages.len().min(23) / 2) * 2 + 1);
+            if r.chance(0.5) {
+                idx.sort();
+            }
+            let update: Vec<String> = idx.iter().map(|i| pages[*i].to_string()).collect();
+            update.join(",")
+        })
+        .collect();
+    format!("{}\n\n{}", rules.join("\n"), updates.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_updates() {
+        for size in [5, 100] {
+            let input = generate(size, &mut Rng::new(5));
+            let lines: Vec<&str> = input.lines().collect();
+            assert!(solve_part1(&lines) > 0);
+            assert!(solve_part2(&lines) > 0);
+        }
+    }
+}
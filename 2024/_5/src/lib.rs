@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
+pub mod generator;
+
 fn build_order_rules(strs: &[&str]) -> HashMap<u32, HashSet<u32>> {
     let mut m: HashMap<u32, HashSet<u32>> = HashMap::new();
 
@@ -113,52 +115,19 @@ pub fn solve_part2(strs: &[&str]) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = "47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_example() {
-        let input: Vec<&str> = INPUT.lines().map(str::trim).collect();
-
-        let res = solve_part1(&input);
-
-        dbg!(res);
-        assert_eq!(res, 143);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()));
     }
+
     #[test]
     fn test_example_part2() {
-        let input: Vec<&str> = INPUT.lines().map(str::trim).collect();
-
-        let res = solve_part2(&input);
-
-        dbg!(res);
-        assert_eq!(res, 123);
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,63 @@
+//! Lab maps with scattered obstructions and a guard facing up.
+
+use std::collections::HashSet;
+
+use aoc_runner::rng::Rng;
+
+/// Whether the guard walks off the map rather than round in circles.
+fn escapes(grid: &[Vec<char>], (mut x, mut y): (usize, usize)) -> bool {
+    let (mut dx, mut dy) = (0isize, -1isize);
+    let mut seen = HashSet::new();
+    while seen.insert((x, y, dx, dy)) {
+        let Some((nx, ny)) = x
+            .checked_add_signed(dx)
+            .zip(y.checked_add_signed(dy))
+            .filter(|(nx, ny)| *ny < grid.len() && *nx < grid[0].len())
+        else {
+            return true;
+        };
+        if grid[ny][nx] == '#' {
+            (dx, dy) = (-dy, dx);
+        } else {
+            (x, y) = (nx, ny);
+        }
+    }
+    false
+}
+
+/// A `size` by `size` map, about one cell in twenty obstructed, on which the
+/// guard eventually leaves.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    loop {
+        let mut grid: Vec<Vec<char>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if r.chance(0.05) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let guard = (r.below(size), r.below(size));
+        grid[guard.1][guard.0] = '^';
+        if escapes(&grid, guard) {
+            return grid
+                .into_iter()
+                .map(|l| l.into_iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_lab() {
+        let input = generate(30, &mut Rng::new(6));
+        let lines: Vec<&str> = input.lines().collect();
+        assert!(solve_part1(&lines) > 0);
+        solve_part2(&lines);
+    }
+}
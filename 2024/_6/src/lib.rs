@@ -1,6 +1,8 @@
 use rayon::prelude::*;
 use std::collections::HashSet;
 
+pub mod generator;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Direction {
     Up,
@@ -83,36 +85,24 @@ fn unique_locations(guard: &Guard, strs: &[&str]) -> HashSet<(usize, usize)> {
     visited
 }
 
-fn has_cycle(guard: &Guard, strs: &[Vec<u8>]) -> bool {
-    let mut visited: HashSet<Guard> = HashSet::new();
+/// The guard's next position and heading, or `None` once they walk off the map.
+fn step(guard: &Guard, strs: &[Vec<u8>]) -> Option<Guard> {
+    let shift = guard.facing.shift();
+    let x = guard.x.checked_add_signed(shift.0)?;
+    let y = guard.y.checked_add_signed(shift.1)?;
+    let c = *strs.get(y)?.get(x)?;
     let mut guard = guard.clone();
-
-    let max_x: isize = strs[0].len() as isize;
-    let max_y: isize = strs.len() as isize;
-
-    visited.insert(guard.clone());
-    loop {
-        let shift = guard.facing.shift();
-        let new_x = guard.x as isize + shift.0;
-        let new_y = guard.y as isize + shift.1;
-        if new_x < 0 || new_x >= max_x || new_y < 0 || new_y >= max_y {
-            break;
-        }
-        let new_x = new_x as usize;
-        let new_y = new_y as usize;
-        let c = strs[new_y][new_x];
-        if c == b'#' {
-            guard.turn();
-        } else {
-            guard.x = new_x;
-            guard.y = new_y;
-            if visited.contains(&guard) {
-                return true;
-            }
-            visited.insert(guard.clone());
-        }
+    if c == b'#' {
+        guard.turn();
+    } else {
+        guard.x = x;
+        guard.y = y;
     }
-    false
+    Some(guard)
+}
+
+fn has_cycle(guard: &Guard, strs: &[Vec<u8>]) -> bool {
+    !aoc_sim::brent(guard.clone(), |g| step(g, strs)).halted()
 }
 
 pub fn solve_part1(strs: &[&str]) -> usize {
@@ -148,6 +138,7 @@ pub fn solve_part2(strs: &[&str]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_fixture::{fixture, puzzle_tests};
     /*      0123456789
            0....#.....
            1.........#
@@ -161,30 +152,9 @@ mod tests {
            9......#...
     */
 
-    const INPUT: &str = "
-        ....#.....
-        .........#
-        ..........
-        ..#.......
-        .......#..
-        ..........
-        .#..^.....
-        ........#.
-        #.........
-        ......#...
-    ";
-
-    fn read_input() -> Vec<&'static str> {
-        INPUT
-            .lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect()
-    }
-
     #[test]
     fn test_find_guard() {
-        let input = read_input();
+        let input = fixture!("example").lines();
 
         let g = find_guard(&input).unwrap();
 
@@ -194,17 +164,15 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let input = read_input();
-        let res = solve_part1(&input);
-
-        assert_eq!(res, 41);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_part2() {
-        let input = read_input();
-        let res = solve_part2(&input);
-
-        assert_eq!(res, 6);
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,50 @@
+//! Calibration equations, most of them solvable.
+
+use aoc_runner::rng::Rng;
+
+/// `size` equations of two to nine numbers. The numbers have at most 18
+/// digits between them, so no way of combining them overflows a `u64`.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let mut numbers = vec![];
+            let mut digits = 0;
+            for _ in 0..r.range(2, 9) {
+                let n = r.range(1, if digits < 16 { 99 } else { 9 });
+                digits += n.to_string().len();
+                numbers.push(n as u64);
+                if digits >= 18 {
+                    break;
+                }
+            }
+            let mut value = numbers[0];
+            for n in numbers[1..].iter() {
+                value = match r.below(3) {
+                    0 => value + n,
+                    1 => value * n,
+                    _ => format!("{}{}", value, n).parse().unwrap(),
+                };
+            }
+            if r.chance(0.3) {
+                value += r.range(1, 9) as u64;
+            }
+            let numbers: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+            format!("{}: {}", value, numbers.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_equations() {
+        let input = generate(100, &mut Rng::new(7));
+        let lines: Vec<&str> = input.lines().collect();
+        let (p1, p2) = (solve_part1(&lines), solve_part2(&lines));
+        assert!(0 < p1 && p1 < p2);
+    }
+}
@@ -1,6 +1,8 @@
 use itertools::Itertools;
 use rayon::prelude::*;
 
+pub mod generator;
+
 fn add(a: u64, b: u64) -> u64 {
     a + b
 }
@@ -10,8 +12,7 @@ fn mul(a: u64, b: u64) -> u64 {
 }
 
 fn concat(a: u64, b: u64) -> u64 {
-    let nn = format!("{}{}", a, b);
-    nn.parse().unwrap()
+    aoc_math::concat(a, b)
 }
 
 type OpFn = fn(u64, u64) -> u64;
@@ -69,42 +70,19 @@ pub fn solve_part2(strs: &[&str]) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = "
-        190: 10 19
-        3267: 81 40 27
-        83: 17 5
-        156: 15 6
-        7290: 6 8 6 15
-        161011: 16 10 13
-        192: 17 8 14
-        21037: 9 7 18 13
-        292: 11 6 16 20
-    ";
-
-    fn read_input() -> Vec<&'static str> {
-        INPUT
-            .lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect()
-    }
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_example_part1() {
-        let input = read_input();
-
-        let res = solve_part1(&input);
-
-        assert_eq!(res, 3749);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_example_part2() {
-        let input = read_input();
-
-        let res = solve_part2(&input);
-
-        assert_eq!(res, 11387);
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,39 @@
+//! Antenna maps: a few antennas per frequency on an empty roof.
+
+use aoc_runner::rng::Rng;
+
+const FREQUENCIES: &str = "0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// A `size` by `size` map with about one antenna in fifty cells, two to five
+/// per frequency.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    let mut grid = vec![vec!['.'; size]; size];
+    let frequencies: Vec<char> = FREQUENCIES.chars().collect();
+    let mut left = (size * size / 50).max(2);
+    while left >= 2 {
+        let f = *r.pick(&frequencies);
+        for _ in 0..r.range(2, 5).min(left) {
+            let (x, y) = (r.below(size), r.below(size));
+            grid[y][x] = f;
+            left -= 1;
+        }
+    }
+    grid.into_iter()
+        .map(|l| l.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_roof() {
+        let input = generate(50, &mut Rng::new(8));
+        let lines: Vec<&str> = input.lines().collect();
+        let (p1, p2) = (solve_part1(&lines), solve_part2(&lines));
+        assert!(0 < p1 && p1 < p2);
+    }
+}
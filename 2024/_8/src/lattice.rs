@@ -0,0 +1,115 @@
+pub type Point = (isize, isize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Bounds {
+    pub fn new(width: usize, height: usize) -> Self {
+        Bounds { width, height }
+    }
+
+    pub fn contains(&self, (x, y): Point) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Direction vector from `a` to `b`. With `reduce` it is divided by the gcd
+/// of its components, so stepping by it visits every lattice point of the line.
+pub fn step(a: Point, b: Point, reduce: bool) -> Point {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    if !reduce {
+        return (dx, dy);
+    }
+    let g = gcd(dx, dy).max(1);
+    (dx / g, dy / g)
+}
+
+/// Points of the line through `a` and `b` that lie inside `bounds`, ordered
+/// from one edge to the other. Without `reduce` only points at whole multiples
+/// of `b - a` from `a` are produced.
+pub fn line_points(a: Point, b: Point, bounds: Bounds, reduce: bool) -> Vec<Point> {
+    let (sx, sy) = step(a, b, reduce);
+    if (sx, sy) == (0, 0) {
+        return if bounds.contains(a) { vec![a] } else { vec![] };
+    }
+
+    let mut start = a;
+    while bounds.contains((start.0 - sx, start.1 - sy)) {
+        start = (start.0 - sx, start.1 - sy);
+    }
+
+    let mut res = vec![];
+    let mut p = start;
+    while bounds.contains(p) {
+        res.push(p);
+        p = (p.0 + sx, p.1 + sy);
+    }
+    res
+}
+
+/// Points on the line through `a` and `b`, outside the segment between them,
+/// where one of the two is `ratio` times as far away as the other.
+pub fn ratio_points(a: Point, b: Point, ratio: isize, bounds: Bounds) -> Vec<Point> {
+    assert!(ratio > 1, "ratio must be greater than 1");
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let k = ratio - 1;
+    if dx % k != 0 || dy % k != 0 {
+        return vec![];
+    }
+    let (sx, sy) = (dx / k, dy / k);
+
+    [(a.0 - sx, a.1 - sy), (b.0 + sx, b.1 + sy)]
+        .into_iter()
+        .filter(|p| bounds.contains(*p))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_points() {
+        let bounds = Bounds::new(10, 10);
+        assert_eq!(
+            ratio_points((4, 3), (5, 5), 2, bounds),
+            vec![(3, 1), (6, 7)]
+        );
+        assert_eq!(
+            ratio_points((5, 5), (4, 3), 2, bounds),
+            vec![(6, 7), (3, 1)]
+        );
+        assert_eq!(ratio_points((8, 4), (4, 3), 2, bounds), vec![(0, 2)]);
+        assert_eq!(ratio_points((0, 0), (3, 3), 4, bounds), vec![(4, 4)]);
+        assert!(ratio_points((0, 0), (1, 1), 3, bounds).is_empty());
+    }
+
+    #[test]
+    fn test_line_points() {
+        let bounds = Bounds::new(10, 10);
+        assert_eq!(
+            line_points((0, 0), (3, 1), bounds, false),
+            vec![(0, 0), (3, 1), (6, 2), (9, 3)]
+        );
+        assert_eq!(
+            line_points((2, 2), (4, 4), bounds, false),
+            vec![(0, 0), (2, 2), (4, 4), (6, 6), (8, 8)]
+        );
+        assert_eq!(line_points((2, 2), (4, 4), bounds, true).len(), 10);
+        assert_eq!(
+            line_points((5, 1), (5, 3), bounds, true),
+            (0..10).map(|y| (5, y)).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,10 +1,14 @@
 use itertools::Itertools;
-use std::cmp::Ordering;
 use std::{
     collections::{HashMap, HashSet},
-    fmt, isize,
+    fmt,
 };
 
+pub mod generator;
+pub mod lattice;
+
+use lattice::{line_points, ratio_points, Bounds, Point};
+
 #[derive(Debug, Clone)]
 struct Tower {
     x: usize,
@@ -23,6 +27,12 @@ impl fmt::Display for Tower {
     }
 }
 
+impl Tower {
+    fn point(&self) -> Point {
+        (self.x as isize, self.y as isize)
+    }
+}
+
 fn find_towers(world: &[&str]) -> HashMap<u8, Vec<Tower>> {
     let mut tm: HashMap<u8, Vec<Tower>> = HashMap::new();
 
@@ -42,245 +52,18 @@ fn find_towers(world: &[&str]) -> HashMap<u8, Vec<Tower>> {
 }
 
 fn find_antinodes(t0: &Tower, t1: &Tower, max_x: usize, max_y: usize) -> Vec<(usize, usize)> {
-    let dx = t1.x.abs_diff(t0.x);
-    let dy = t1.y.abs_diff(t0.y);
-
-    let mut res: Vec<(isize, isize)> = vec![];
-
-    match (t0.x.cmp(&t1.x), t0.y.cmp(&t1.y)) {
-        (Ordering::Equal, Ordering::Less) => {
-            let y0 = t0.y as isize - dy as isize;
-            let y1 = t1.y as isize + dy as isize;
-            res.push((t0.x as isize, y0));
-            res.push((t0.x as isize, y1));
-        }
-        (Ordering::Equal, Ordering::Greater) => {
-            let y0 = t1.y as isize - dy as isize;
-            let y1 = t0.y as isize + dy as isize;
-            res.push((t0.x as isize, y0));
-            res.push((t0.x as isize, y1));
-        }
-
-        (Ordering::Less, Ordering::Equal) => {
-            let x0 = t0.x as isize - dx as isize;
-            let x1 = t1.x as isize + dx as isize;
-            res.push((x0, t0.y as isize));
-            res.push((x1, t0.y as isize));
-        }
-        (Ordering::Greater, Ordering::Equal) => {
-            let x0 = t1.x as isize - dx as isize;
-            let x1 = t0.x as isize + dx as isize;
-            res.push((x0, t0.y as isize));
-            res.push((x1, t0.y as isize));
-        }
-
-        (Ordering::Less, Ordering::Less) => {
-            let x0 = t0.x as isize - dx as isize;
-            let x1 = t1.x as isize + dx as isize;
-
-            let y0 = t0.y as isize - dy as isize;
-            let y1 = t1.y as isize + dy as isize;
-
-            res.push((x0, y0));
-            res.push((x1, y1));
-        }
-        (Ordering::Less, Ordering::Greater) => {
-            let x0 = t0.x as isize - dx as isize;
-            let x1 = t1.x as isize + dx as isize;
-
-            let y0 = t0.y as isize + dy as isize;
-            let y1 = t1.y as isize - dy as isize;
-
-            res.push((x0, y0));
-            res.push((x1, y1));
-        }
-
-        (Ordering::Greater, Ordering::Less) => {
-            let x0 = t0.x as isize + dx as isize;
-            let x1 = t1.x as isize - dx as isize;
-
-            let y0 = t0.y as isize - dy as isize;
-            let y1 = t1.y as isize + dy as isize;
-
-            res.push((x0, y0));
-            res.push((x1, y1));
-        }
-        (Ordering::Greater, Ordering::Greater) => {
-            let x0 = t0.x as isize + dx as isize;
-            let x1 = t1.x as isize - dx as isize;
-
-            let y0 = t0.y as isize + dy as isize;
-            let y1 = t1.y as isize - dy as isize;
-
-            res.push((x0, y0));
-            res.push((x1, y1));
-        }
-        (_, _) => unreachable!(),
-    }
-
-    res.iter()
-        .filter_map(|(x, y)| {
-            if *x >= 0 && *x < max_x as isize && *y >= 0 && *y < max_y as isize {
-                return Some((*x as usize, *y as usize));
-            }
-            None
-        })
-        .collect()
+    let bounds = Bounds::new(max_x, max_y);
+    to_cells(ratio_points(t0.point(), t1.point(), 2, bounds))
 }
 
 fn find_antinodes_t(t0: &Tower, t1: &Tower, max_x: usize, max_y: usize) -> Vec<(usize, usize)> {
-    let dx = t1.x.abs_diff(t0.x);
-    let dy = t1.y.abs_diff(t0.y);
-
-    let mut res: Vec<(isize, isize)> = vec![];
-
-    res.push((t0.x as isize, t0.y as isize));
-    res.push((t1.x as isize, t1.y as isize));
-
-    match (t0.x.cmp(&t1.x), t0.y.cmp(&t1.y)) {
-        (Ordering::Equal, Ordering::Less) => {
-            let mut y0 = t0.y as isize - dy as isize;
-            while y0 >= 0 {
-                res.push((t0.x as isize, y0));
-                y0 -= dy as isize;
-            }
-
-            let mut y1 = t1.y as isize + dy as isize;
-            while y1 < max_y as isize {
-                res.push((t0.x as isize, y1));
-                y1 += dy as isize;
-            }
-        }
-        (Ordering::Equal, Ordering::Greater) => {
-            let mut y0 = t1.y as isize - dy as isize;
-            while y0 >= 0 {
-                res.push((t0.x as isize, y0));
-                y0 -= dy as isize;
-            }
-
-            let mut y1 = t0.y as isize + dy as isize;
-            while y1 < max_y as isize {
-                res.push((t0.x as isize, y1));
-                y1 += dy as isize;
-            }
-        }
-
-        (Ordering::Less, Ordering::Equal) => {
-            let mut x0 = t0.x as isize - dx as isize;
-            while x0 >= 0 {
-                res.push((x0, t0.y as isize));
-                x0 -= dx as isize;
-            }
-
-            let mut x1 = t1.x as isize + dx as isize;
-
-            while x1 < max_x as isize {
-                res.push((x1, t0.y as isize));
-                x1 += dx as isize;
-            }
-        }
-        (Ordering::Greater, Ordering::Equal) => {
-            let mut x0 = t1.x as isize - dx as isize;
-
-            while x0 >= 0 {
-                res.push((x0, t0.y as isize));
-                x0 -= dx as isize;
-            }
-
-            let mut x1 = t0.x as isize + dx as isize;
-            while x1 < max_x as isize {
-                res.push((x1, t0.y as isize));
-                x1 += dx as isize;
-            }
-        }
-
-        (Ordering::Less, Ordering::Less) => {
-            let mut x0 = t0.x as isize - dx as isize;
-            let mut y0 = t0.y as isize - dy as isize;
-
-            while x0 >= 0 && y0 >= 0 {
-                res.push((x0, y0));
-                x0 -= dx as isize;
-                y0 -= dy as isize;
-            }
-
-            let mut x1 = t1.x as isize + dx as isize;
-            let mut y1 = t1.y as isize + dy as isize;
-
-            while x1 < max_x as isize && y1 < max_y as isize {
-                res.push((x1, y1));
-                x1 += dx as isize;
-                y1 += dy as isize;
-            }
-        }
-        (Ordering::Less, Ordering::Greater) => {
-            let mut x0 = t0.x as isize - dx as isize;
-            let mut y0 = t0.y as isize + dy as isize;
-
-            while x0 >= 0 && y0 < max_y as isize {
-                res.push((x0, y0));
-                x0 -= dx as isize;
-                y0 += dy as isize;
-            }
-
-            let mut x1 = t1.x as isize + dx as isize;
-            let mut y1 = t1.y as isize - dy as isize;
-
-            while x1 < max_x as isize && y1 >= 0 {
-                res.push((x1, y1));
-                x1 += dx as isize;
-                y1 -= dy as isize;
-            }
-        }
-
-        (Ordering::Greater, Ordering::Less) => {
-            let mut x0 = t0.x as isize + dx as isize;
-            let mut y0 = t0.y as isize - dy as isize;
-
-            while x0 < max_x as isize && y0 >= 0 {
-                res.push((x0, y0));
-                x0 += dx as isize;
-                y0 -= dy as isize;
-            }
-
-            let mut x1 = t1.x as isize - dx as isize;
-            let mut y1 = t1.y as isize + dy as isize;
-
-            while x1 >= 0 && y1 < max_y as isize {
-                res.push((x1, y1));
-                x1 -= dx as isize;
-                y1 += dy as isize;
-            }
-        }
-        (Ordering::Greater, Ordering::Greater) => {
-            let mut x0 = t0.x as isize + dx as isize;
-            let mut y0 = t0.y as isize + dy as isize;
-
-            while x0 < max_x as isize && y0 < max_y as isize {
-                res.push((x0, y0));
-                x0 += dx as isize;
-                y0 += dy as isize;
-            }
-
-            let mut x1 = t1.x as isize - dx as isize;
-            let mut y1 = t1.y as isize - dy as isize;
-
-            while x1 >= 0 && y1 >= 0 {
-                res.push((x1, y1));
-                x1 -= dx as isize;
-                y1 -= dy as isize;
-            }
-        }
-        (_, _) => unreachable!(),
-    }
+    let bounds = Bounds::new(max_x, max_y);
+    to_cells(line_points(t0.point(), t1.point(), bounds, true))
+}
 
-    res.iter()
-        .filter_map(|(x, y)| {
-            if *x >= 0 && *x < max_x as isize && *y >= 0 && *y < max_y as isize {
-                return Some((*x as usize, *y as usize));
-            }
-            None
-        })
+fn to_cells(ps: Vec<Point>) -> Vec<(usize, usize)> {
+    ps.into_iter()
+        .map(|(x, y)| (x as usize, y as usize))
         .collect()
 }
 
@@ -288,48 +71,85 @@ fn all_pairs(ts: &[Tower]) -> Vec<(&Tower, &Tower)> {
     ts.iter().combinations(2).map(|v| (v[0], v[1])).collect()
 }
 
-pub fn uniq_antinodes(strs: &[&str]) -> HashSet<(usize, usize)> {
+type FindAntinodes = fn(&Tower, &Tower, usize, usize) -> Vec<(usize, usize)>;
+
+fn tower_antinodes(
+    ts: &[Tower],
+    max_x: usize,
+    max_y: usize,
+    find: FindAntinodes,
+) -> HashSet<(usize, usize)> {
+    all_pairs(ts)
+        .iter()
+        .flat_map(|(t0, t1)| find(t0, t1, max_x, max_y))
+        .collect()
+}
+
+fn collect_antinodes(strs: &[&str], find: FindAntinodes) -> HashSet<(usize, usize)> {
     let max_x = strs[0].len();
     let max_y = strs.len();
-    let ts = find_towers(strs);
-
-    let mut uniq_antitowers: HashSet<(usize, usize)> = HashSet::new();
-
-    ts.values().for_each(|x| {
-        let ps = all_pairs(x);
-        ps.iter().for_each(|(t0, t1)| {
-            let a = find_antinodes(t0, t1, max_x, max_y);
-            //println!("for pair: {} {}", t0, t1);
-            //println!("antinodes: {:?}", a);
-            for p in a {
-                uniq_antitowers.insert(p);
-            }
-        });
-    });
+    find_towers(strs)
+        .values()
+        .flat_map(|ts| tower_antinodes(ts, max_x, max_y, find))
+        .collect()
+}
 
-    uniq_antitowers
+pub fn uniq_antinodes(strs: &[&str]) -> HashSet<(usize, usize)> {
+    collect_antinodes(strs, find_antinodes)
 }
 
 pub fn uniq_antinodes_t(strs: &[&str]) -> HashSet<(usize, usize)> {
-    let max_x = strs[0].len();
-    let max_y = strs.len();
-    let ts = find_towers(strs);
+    collect_antinodes(strs, find_antinodes_t)
+}
 
-    let mut uniq_antitowers: HashSet<(usize, usize)> = HashSet::new();
+pub fn frequencies(strs: &[&str]) -> Vec<u8> {
+    find_towers(strs).into_keys().sorted().collect()
+}
 
-    ts.values().for_each(|x| {
-        let ps = all_pairs(x);
-        ps.iter().for_each(|(t0, t1)| {
-            let a = find_antinodes_t(t0, t1, max_x, max_y);
-            //println!("for pair: {} {}", t0, t1);
-            //println!("antinodes: {:?}", a);
-            for p in a {
-                uniq_antitowers.insert(p);
-            }
-        });
-    });
+/// Map of `.` with every antinode marked `#`.
+pub fn render_antinodes(
+    max_x: usize,
+    max_y: usize,
+    antinodes: &HashSet<(usize, usize)>,
+) -> Vec<String> {
+    (0..max_y)
+        .map(|y| {
+            (0..max_x)
+                .map(|x| {
+                    if antinodes.contains(&(x, y)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
 
-    uniq_antitowers
+/// Antinodes of a single frequency drawn over its antennas, the way the
+/// puzzle shows them: an antenna hides an antinode on the same cell.
+pub fn render_overlay(strs: &[&str], freq: u8, resonant: bool) -> Vec<String> {
+    let max_x = strs[0].len();
+    let max_y = strs.len();
+    let towers = find_towers(strs).remove(&freq).unwrap_or_default();
+    let find: FindAntinodes = if resonant {
+        find_antinodes_t
+    } else {
+        find_antinodes
+    };
+    let antinodes = tower_antinodes(&towers, max_x, max_y, find);
+
+    let mut grid: Vec<Vec<u8>> = render_antinodes(max_x, max_y, &antinodes)
+        .into_iter()
+        .map(String::into_bytes)
+        .collect();
+    for t in towers.iter() {
+        grid[t.y][t.x] = t.ch;
+    }
+    grid.into_iter()
+        .map(|l| String::from_utf8(l).expect("Map is not utf8"))
+        .collect()
 }
 
 pub fn solve_part1(strs: &[&str]) -> u32 {
@@ -342,80 +162,12 @@ pub fn solve_part2(strs: &[&str]) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use std::str::from_utf8;
-
     use super::*;
-
-    const INPUT: &str = "
-        ............
-        ........0...
-        .....0......
-        .......0....
-        ....0.......
-        ......A.....
-        ............
-        ............
-        ........A...
-        .........A..
-        ............
-        ............
-    ";
-
-    const EMPTY: &str = "
-        ............
-        ............
-        ............
-        ............
-        ............
-        ............
-        ............
-        ............
-        ............
-        ............
-        ............
-        ............
-
-    ";
-
-    const EXPECTED: &str = "
-        ......#....#
-        ...#........
-        ....#.....#.
-        ..#.........
-        .........#..
-        .#....#.....
-        ...#........
-        #......#....
-        ............
-        ............
-        ..........#.
-        ..........#.
-    ";
-
-    /*
-        ............
-        ............
-        ............
-        ............
-        ............
-        ......A.....
-        ............
-        ............
-        ............
-        .........A..
-        ............
-        ............
-    */
-    fn read_input(inp: &str) -> Vec<&str> {
-        inp.lines()
-            .map(str::trim)
-            .filter(|x| !x.is_empty())
-            .collect()
-    }
+    use aoc_fixture::{fixture, puzzle_tests};
 
     #[test]
     fn test_find_antinodes() {
-        let input = read_input(INPUT);
+        let input = fixture!("example").lines();
         let max_x = input[0].len();
         let max_y = input.len();
         let t0 = Tower {
@@ -429,11 +181,12 @@ mod tests {
             y: 3,
         };
         let r = find_antinodes(&t0, &t1, max_x, max_y);
+        assert_eq!(r, vec![(3, 1), (9, 4)]);
     }
 
     #[test]
     fn test_special() {
-        let input = read_input(INPUT);
+        let input = fixture!("example").lines();
         let max_x = input[0].len();
         let max_y = input.len();
         let t0 = Tower {
@@ -447,41 +200,36 @@ mod tests {
             y: 9,
         };
         let r = find_antinodes(&t0, &t1, max_x, max_y);
-        dbg!(r);
+        assert_eq!(r, vec![(3, 1)]);
     }
 
     #[test]
     fn test_part1_example() {
-        let input = read_input(INPUT);
+        let input = fixture!("example").lines();
         let ts = uniq_antinodes(&input);
+        let res = render_antinodes(input[0].len(), input.len(), &ts);
+        assert_eq!(res, fixture!("antinodes").lines());
+    }
 
-        let emp = read_input(EMPTY);
-        let mut emp: Vec<Vec<u8>> = emp.iter().map(|x| Vec::from(x.as_bytes())).collect();
-
-        let ex = read_input(EXPECTED);
-
-        for an in ts.iter() {
-            emp[an.1][an.0] = b'#';
-        }
-
-        for (line, exline) in emp.iter().zip(ex) {
-            let line = from_utf8(line).unwrap();
-            println!("{} {}", line, exline);
-            //assert_eq!(line, exline);
-        }
+    #[test]
+    fn test_overlay() {
+        let input = fixture!("overlay").lines();
+        let expected = fixture!("overlay_antinodes").lines();
+        assert_eq!(frequencies(&input), vec![b'A', b'a']);
+        assert_eq!(render_overlay(&input, b'a', false), expected);
     }
 
     #[test]
     fn test_part1() {
-        let input = read_input(INPUT);
-        let res = solve_part1(&input);
-        assert_eq!(res, 14);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(&f.lines()));
     }
 
     #[test]
     fn test_part2() {
-        let input = read_input(INPUT);
-        let res = solve_part2(&input);
-        assert_eq!(res, 34);
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(&f.lines()));
     }
+
+    puzzle_tests!(|f| solve_part1(&f.lines()), |f| solve_part2(&f.lines()));
 }
@@ -0,0 +1,233 @@
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+use aoc_math::range_sum;
+
+/// Contiguous run of blocks belonging to one file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent {
+    pub id: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Extent {
+    fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    fn checksum(&self) -> usize {
+        match self.len {
+            0 => 0,
+            len => self.id * range_sum(self.start, self.start + len - 1),
+        }
+    }
+}
+
+/// Free span of blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub len: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Disk {
+    extents: Vec<Extent>,
+    free: Vec<Span>,
+    size: usize,
+    checksum: usize,
+}
+
+impl Disk {
+    pub fn parse(s: &str) -> Disk {
+        let mut extents = vec![];
+        let mut free = vec![];
+        let mut pos = 0;
+        for (i, b) in s.trim().bytes().enumerate() {
+            let len = (b - b'0') as usize;
+            if i % 2 == 0 {
+                extents.push(Extent {
+                    id: i / 2,
+                    start: pos,
+                    len,
+                });
+            } else if len > 0 {
+                free.push(Span { start: pos, len });
+            }
+            pos += len;
+        }
+        let checksum = extents.iter().map(Extent::checksum).sum();
+        Disk {
+            extents,
+            free,
+            size: pos,
+            checksum,
+        }
+    }
+
+    pub fn checksum(&self) -> usize {
+        self.checksum
+    }
+
+    pub fn extents(&self) -> &[Extent] {
+        &self.extents
+    }
+
+    fn relocate(&mut self, piece: Extent, start: usize) -> Extent {
+        let moved = Extent { start, ..piece };
+        self.checksum -= piece.checksum();
+        self.checksum += moved.checksum();
+        moved
+    }
+
+    /// Moves single blocks from the end of the disk into the leftmost free
+    /// block until there are no gaps between files.
+    pub fn compact_blocks(&mut self) {
+        let mut files = std::mem::take(&mut self.extents);
+        files.sort_by_key(|e| e.start);
+        let mut placed = vec![];
+        let mut free = vec![];
+
+        for gap in std::mem::take(&mut self.free) {
+            let mut used = 0;
+            while used < gap.len {
+                let Some(last) = files.last_mut() else {
+                    break;
+                };
+                if last.start < gap.start {
+                    break;
+                }
+                if last.len == 0 {
+                    files.pop();
+                    continue;
+                }
+                let n = last.len.min(gap.len - used);
+                let piece = Extent {
+                    id: last.id,
+                    start: last.end() - n,
+                    len: n,
+                };
+                last.len -= n;
+                if last.len == 0 {
+                    files.pop();
+                }
+                placed.push(self.relocate(piece, gap.start + used));
+                used += n;
+            }
+            if used < gap.len {
+                free.push(Span {
+                    start: gap.start + used,
+                    len: gap.len - used,
+                });
+            }
+        }
+
+        files.extend(placed);
+        files.sort_by_key(|e| e.start);
+        self.extents = files;
+        self.free = free;
+    }
+
+    /// Moves every file, highest id first, into the leftmost free span that
+    /// fits it whole, if that span is to the left of the file.
+    pub fn compact_files(&mut self) {
+        // Free spans bucketed by size, each bucket a min-heap of start positions.
+        let max_len = self.free.iter().map(|s| s.len).max().unwrap_or(0);
+        let mut by_len: Vec<BinaryHeap<Reverse<usize>>> = vec![BinaryHeap::new(); max_len + 1];
+        for s in self.free.iter() {
+            by_len[s.len].push(Reverse(s.start));
+        }
+
+        let mut files = std::mem::take(&mut self.extents);
+        files.sort_by_key(|e| Reverse(e.id));
+        for f in files.iter_mut().filter(|f| f.len > 0) {
+            let best = (f.len..by_len.len())
+                .filter_map(|len| by_len[len].peek().map(|Reverse(start)| (*start, len)))
+                .filter(|(start, _)| *start < f.start)
+                .min();
+            let Some((start, len)) = best else {
+                continue;
+            };
+            by_len[len].pop();
+            if len > f.len {
+                by_len[len - f.len].push(Reverse(start + f.len));
+            }
+            *f = self.relocate(*f, start);
+        }
+
+        files.sort_by_key(|e| e.start);
+        self.extents = files;
+        self.free = self.gaps();
+    }
+
+    fn gaps(&self) -> Vec<Span> {
+        let mut res = vec![];
+        let mut pos = 0;
+        for e in self.extents.iter() {
+            if e.start > pos {
+                res.push(Span {
+                    start: pos,
+                    len: e.start - pos,
+                });
+            }
+            pos = pos.max(e.end());
+        }
+        if pos < self.size {
+            res.push(Span {
+                start: pos,
+                len: self.size - pos,
+            });
+        }
+        res
+    }
+}
+
+/// Renders the disk as in the puzzle, one character per block: the last
+/// digit of the file id, or `.` for free space.
+impl fmt::Display for Disk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut blocks = vec!['.'; self.size];
+        for e in self.extents.iter() {
+            let ch = char::from_digit((e.id % 10) as u32, 10).unwrap();
+            blocks[e.start..e.end()].fill(ch);
+        }
+        write!(f, "{}", blocks.into_iter().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "2333133121414131402";
+
+    #[test]
+    fn test_render() {
+        assert_eq!(Disk::parse("12345").to_string(), "0..111....22222");
+        assert_eq!(
+            Disk::parse(INPUT).to_string(),
+            "00...111...2...333.44.5555.6666.777.888899"
+        );
+    }
+
+    #[test]
+    fn test_compact_blocks() {
+        let mut d = Disk::parse("12345");
+        d.compact_blocks();
+        assert_eq!(d.to_string(), "022111222......");
+        assert_eq!(d.checksum(), 60);
+
+        let mut d = Disk::parse(INPUT);
+        d.compact_blocks();
+        assert_eq!(d.to_string(), "0099811188827773336446555566..............");
+        assert_eq!(d.checksum(), 1928);
+    }
+
+    #[test]
+    fn test_compact_files() {
+        let mut d = Disk::parse(INPUT);
+        d.compact_files();
+        assert_eq!(d.to_string(), "00992111777.44.333....5555.6666.....8888..");
+        assert_eq!(d.checksum(), 2858);
+    }
+}
@@ -0,0 +1,24 @@
+//! Disk maps: files of 1 to 9 blocks between gaps of 0 to 9.
+
+use aoc_runner::rng::Rng;
+
+/// `size` digits, ending in a gap when `size` is even.
+pub fn generate(size: usize, r: &mut Rng) -> String {
+    (0..size)
+        .map(|i| char::from(b'0' + r.range((i % 2 == 0) as usize, 9) as u8))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solve_part1, solve_part2};
+
+    #[test]
+    fn test_generated_disk() {
+        let input = generate(999, &mut Rng::new(9));
+        assert_eq!(input.len(), 999);
+        assert!(solve_part1(&input) > 0);
+        assert!(solve_part2(&input) > 0);
+    }
+}
@@ -1,15 +1,18 @@
-use std::{fmt, iter, usize};
+use std::fmt;
+
+pub mod disk;
+pub mod generator;
+
+use aoc_math::range_sum;
+use aoc_runner::diff::Variant;
+use disk::Disk;
 
 fn as_number(x: u8) -> usize {
     (x - b'0') as usize
 }
 
-fn seq_sum(start: usize, end: usize) -> usize {
-    (end + 1 - start) * (start + end) / 2
-}
-
 fn is_file(n: usize) -> bool {
-    n % 2 == 0
+    n.is_multiple_of(2)
 }
 
 #[derive(Debug)]
@@ -25,7 +28,7 @@ impl fmt::Display for File {
 }
 
 fn checksum(id: usize, start: usize, end: usize) -> usize {
-    id * seq_sum(start, end)
+    id * range_sum(start, end)
 }
 
 impl File {
@@ -49,63 +52,13 @@ impl File {
     }
 }
 
-fn parse(s: &str) -> Vec<Option<usize>> {
-    let mut is_file = true;
-    let mut id = 0;
-    let mut res = vec![];
-    for b in s.trim().bytes() {
-        let len = as_number(b);
-        if is_file {
-            res.extend(iter::repeat_n(Some(id), len));
-            id += 1;
-        } else {
-            res.extend(iter::repeat_n(None, len));
-        }
-        is_file = !is_file;
-    }
-    res
-}
-
-pub fn p2(s: &str) -> usize {
-    let mut nums = parse(s);
-    let mut indices: Vec<(usize, Option<usize>)> = nums.clone().into_iter().enumerate().collect();
-    let mut skip = 0;
-    loop {
-        let Some((right, len)) = indices
-            .chunk_by(|a, b| a.1 == b.1)
-            .filter(|p| p[0].1.is_some())
-            .rev()
-            .nth(skip)
-            .map(|p| (p[0].0, p.len()))
-        else {
-            break;
-        };
-        if right == 0 {
-            break;
-        }
-        let Some(left) = indices
-            .chunk_by(|a, b| a.1 == b.1)
-            .find(|p| p.len() >= len && p[0].1.is_none() && p[0].0 < right)
-            .map(|p| p[0].0)
-        else {
-            skip += 1;
-            continue;
-        };
-        for i in 0..len {
-            nums.swap(left + i, right + i);
-        }
-        indices = nums.clone().into_iter().enumerate().collect();
-    }
-    nums.into_iter()
-        .enumerate()
-        .filter_map(|(i, opt)| opt.map(|v| v * i))
-        .sum()
-}
-
-pub fn solve_part1(s: &str) -> usize {
-    let s = s.as_bytes();
+/// Block compaction without materialising the disk: walks files from both
+/// ends and accumulates the checksum as blocks are moved.
+pub fn solve_part1_streaming(s: &str) -> usize {
+    let s = s.trim().as_bytes();
     let mut i: usize = 0; // skip first file
-    let mut j: usize = s.len() - 1;
+                          // The last file, skipping a trailing gap.
+    let mut j: usize = (s.len() - 1) & !1;
     let mut checksum: usize = 0;
     let mut virtual_i = 0;
 
@@ -131,7 +84,10 @@ pub fn solve_part1(s: &str) -> usize {
                 i += 1;
             }
             j -= 2;
-            file_to_move = File::new(j, as_number(s[j]));
+            // The file at `j` may already have been counted in place; at `j ==
+            // i` it is the next file, which has not.
+            let size = if j >= i { as_number(s[j]) } else { 0 };
+            file_to_move = File::new(j, size);
         } else {
             let start = virtual_i;
             let a = file_to_move.move_n_digits(start, free_memory_left);
@@ -145,16 +101,31 @@ pub fn solve_part1(s: &str) -> usize {
     checksum
 }
 
-pub fn solve_part2(str: &str) -> usize {
-    p2(str)
+pub fn solve_part1(s: &str) -> usize {
+    let mut disk = Disk::parse(s);
+    disk.compact_blocks();
+    disk.checksum()
+}
+
+type Solver = fn(&str) -> usize;
+
+/// Part 1 implementations for `--impl`, the block model first.
+pub const PART1: &[Variant<Solver>] = &[
+    ("blocks", solve_part1),
+    ("streaming", solve_part1_streaming),
+];
+
+pub fn solve_part2(s: &str) -> usize {
+    let mut disk = Disk::parse(s);
+    disk.compact_files();
+    disk.checksum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const INPUT: &str = "2333133121414131402";
-    const INPUT2: &str = "12345";
+    use aoc_fixture::{fixture, puzzle_tests};
+    use aoc_runner::diff::Check;
 
     const INPUT3: &str = "233313312141413140258172424";
 
@@ -166,20 +137,45 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let r = parse(INPUT3);
-        dbg!(r);
+        let d = Disk::parse(INPUT3);
+        assert_eq!(d.extents().len(), 14);
     }
 
     #[test]
     fn test_example0() {
-        let res = solve_part1(INPUT2);
-        assert_eq!(res, 60);
+        let f = fixture!("small");
+        f.assert_part1(solve_part1(f.input()));
+        f.assert_part1(solve_part1_streaming(f.input()));
     }
 
     #[test]
     fn test_example1() {
-        dbg!(seq_sum(4, 4));
-        let res = solve_part1(INPUT);
-        assert_eq!(res, 1928);
+        let f = fixture!("example");
+        f.assert_part1(solve_part1(f.input()));
+        f.assert_part1(solve_part1_streaming(f.input()));
     }
+
+    #[test]
+    fn test_example1_part2() {
+        let f = fixture!("example");
+        f.assert_part2(solve_part2(f.input()));
+    }
+
+    /// Files of 1 to 9 blocks separated by gaps of 0 to 9, as in the puzzle,
+    /// possibly followed by a trailing gap.
+    fn is_disk_map(s: &str) -> bool {
+        !s.is_empty()
+            && s.bytes().all(|b| b.is_ascii_digit())
+            && s.bytes().step_by(2).all(|b| b != b'0')
+    }
+
+    #[test]
+    fn test_part1_variants_agree() {
+        Check::new(PART1)
+            .cases(500)
+            .valid(is_disk_map)
+            .assert_agree(|f, s| f(s), |r| generator::generate(r.range(1, 19), r));
+    }
+
+    puzzle_tests!(|f| solve_part1(f.input()), |f| solve_part2(f.input()));
 }
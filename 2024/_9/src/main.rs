@@ -1,25 +1,17 @@
-use std::{
-    fs,
-    io::{self, BufRead},
-    path::Path,
-};
+use std::io;
 
-use _2024_9::{solve_part1, solve_part2};
+use aoc_runner::Day;
 
-fn main() -> io::Result<()> {
-    let dir = Path::new(file!()).parent().unwrap();
-    let input_path = dir.join("../input");
-    let input = fs::File::open(input_path)?;
-    let r = io::BufReader::new(input).lines();
-    let v: Vec<String> = r.map(|x| x.expect("Failed to read file")).collect();
-    let v: Vec<&str> = v.iter().map(String::as_str).collect();
-    println!("Part1:");
-    let res = solve_part1(v[0]);
-    println!("{}", res);
+use _2024_9::{generator, solve_part2, PART1};
 
-    println!("Part2:");
-    let res = solve_part2(v[0]);
-    println!("{}", res);
+fn main() -> io::Result<()> {
+    if aoc_runner::generate(generator::generate)? {
+        return Ok(());
+    }
+    let mut day = Day::from_args(aoc_input::input!()?)?;
+    let part1 = day.variant(PART1);
+    day.part(1, move |i| part1(i.text()));
+    day.part(2, |i| solve_part2(i.text()));
 
-    Ok(())
+    day.finish()
 }
@@ -0,0 +1,18 @@
+//! Regenerates `fixtures/puzzle{1,2}.txt` for every `<year>/_<day>` under
+//! the given root (the current directory by default) and lists the parts
+//! whose expected answer could not be found in `puzzle.md`.
+
+use std::{env, io, path::PathBuf};
+
+use aoc_fixture::puzzle::{days, write_fixtures};
+
+fn main() -> io::Result<()> {
+    let root = PathBuf::from(env::args().nth(1).unwrap_or_else(|| ".".to_string()));
+    for day in days(&root)? {
+        let missing = write_fixtures(&day)?;
+        for part in missing {
+            println!("{}: no expected answer for part {}", day.display(), part);
+        }
+    }
+    Ok(())
+}
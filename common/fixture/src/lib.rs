@@ -0,0 +1,178 @@
+//! Example fixtures shared by the puzzle crates' tests.
+//!
+//! A fixture lives in `fixtures/<name>.txt` next to a crate's `Cargo.toml`.
+//! It starts with `key: value` lines, conventionally `part1` and `part2` for
+//! the expected answers plus any parameters the example needs, followed by a
+//! `---` line and the example input. Both the format and the normalising of
+//! the input are `aoc-input`'s, which reads the same files for `--example`:
+//!
+//! ```text
+//! part1: 142
+//! ---
+//! 1abc2
+//! pqr3stu8vwx
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+pub mod puzzle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub name: &'static str,
+    params: Vec<(String, String)>,
+    input: &'static str,
+}
+
+/// Loads `fixtures/<name>.txt` of the calling crate at compile time.
+#[macro_export]
+macro_rules! fixture {
+    ($name:literal) => {
+        $crate::Fixture::parse(
+            $name,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/fixtures/",
+                $name,
+                ".txt"
+            )),
+        )
+    };
+}
+
+/// Tests a day's solvers against the `puzzle1`/`puzzle2` fixtures extracted
+/// from its `puzzle.md` by `extract-fixtures`. Each argument is called with
+/// the fixture and returns the answer; parts flagged `missing` are skipped.
+#[macro_export]
+macro_rules! puzzle_tests {
+    ($part1:expr, $part2:expr $(,)?) => {
+        #[test]
+        fn test_puzzle_part1() {
+            $crate::check_puzzle($crate::fixture!("puzzle1"), "part1", $part1);
+        }
+
+        #[test]
+        fn test_puzzle_part2() {
+            $crate::check_puzzle($crate::fixture!("puzzle2"), "part2", $part2);
+        }
+    };
+}
+
+/// Asserts `solve(&f)` against the fixture's `key` answer unless the
+/// fixture is marked as missing one.
+pub fn check_puzzle<T: Display>(f: Fixture, key: &str, solve: impl Fn(&Fixture) -> T) {
+    if let Some(reason) = f.get(puzzle::MISSING) {
+        eprintln!("Skipping fixture {}: {}", f.name, reason);
+        return;
+    }
+    f.check(key, solve(&f));
+}
+
+impl Fixture {
+    pub fn parse(name: &'static str, text: &'static str) -> Fixture {
+        let (header, input) = aoc_input::split_fixture(text)
+            .unwrap_or_else(|| panic!("Fixture {} has no --- line", name));
+        let params =
+            aoc_input::parse_answers(header).unwrap_or_else(|e| panic!("Fixture {}: {}", name, e));
+        // Fixtures live for the whole test run, so an input that normalising
+        // changes beyond its end is leaked rather than borrowed.
+        let normalised = aoc_input::normalise(input);
+        let input = match input.starts_with(normalised.as_str()) {
+            true => &input[..normalised.len()],
+            false => normalised.leak(),
+        };
+        Fixture {
+            name,
+            params,
+            input,
+        }
+    }
+
+    pub fn input(&self) -> &'static str {
+        self.input
+    }
+
+    pub fn lines(&self) -> Vec<&'static str> {
+        self.input.lines().collect()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn param<T: FromStr>(&self, key: &str) -> T {
+        let v = self
+            .get(key)
+            .unwrap_or_else(|| panic!("Fixture {} has no {}", self.name, key));
+        v.parse()
+            .unwrap_or_else(|_| panic!("Fixture {}: cannot parse {} = {:?}", self.name, key, v))
+    }
+
+    fn check(&self, key: &str, actual: impl Display) {
+        let expected = self
+            .get(key)
+            .unwrap_or_else(|| panic!("Fixture {} has no {}", self.name, key));
+        assert_eq!(
+            actual.to_string(),
+            expected,
+            "{} of fixture {}",
+            key,
+            self.name
+        );
+    }
+
+    /// Asserts that `actual` prints as the fixture's `part1` answer.
+    pub fn assert_part1(&self, actual: impl Display) {
+        self.check("part1", actual);
+    }
+
+    /// Asserts that `actual` prints as the fixture's `part2` answer.
+    pub fn assert_part2(&self, actual: impl Display) {
+        self.check("part2", actual);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let f = Fixture::parse("t", "# note\npart1: 7\nsize: 11\n---\n#.#\n\n..#\n");
+        assert_eq!(f.lines(), vec!["#.#", "", "..#"]);
+        assert_eq!(f.input(), "#.#\n\n..#");
+        assert_eq!(f.get("part2"), None);
+        assert_eq!(f.param::<usize>("size"), 11);
+        f.assert_part1(7);
+    }
+
+    #[test]
+    fn test_parse_crlf() {
+        let f = Fixture::parse("t", "part1: 7\r\n---\r\n#.#\r\n..#\r\n\r\n");
+        assert_eq!(f.input(), "#.#\n..#");
+        assert_eq!(f.get("part1"), Some("7"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Fixture t: \"part1 7\" is not key: value")]
+    fn test_bad_header() {
+        Fixture::parse("t", "part1 7\n---\n");
+    }
+
+    #[test]
+    fn test_check_puzzle() {
+        let f = Fixture::parse("t", "part2: 3\n---\n1\n2\n");
+        check_puzzle(f, "part2", |f| f.lines().len() + 1);
+        let f = Fixture::parse("t", "missing: no answer\n---\n");
+        check_puzzle(f, "part2", |_| -> u32 { unreachable!() });
+    }
+
+    #[test]
+    #[should_panic(expected = "part1 of fixture t")]
+    fn test_mismatch() {
+        Fixture::parse("t", "part1: 7\n---\n").assert_part1(8);
+    }
+}
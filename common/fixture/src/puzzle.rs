@@ -0,0 +1,261 @@
+//! Pulls examples and their highlighted answers out of a day's `puzzle.md`.
+//!
+//! Each part's text runs until its "Answer:" / "Your puzzle answer was" line.
+//! Fenced blocks introduced by a sentence like "For example:" or "Here's a
+//! larger example:" are taken as example inputs; other blocks show
+//! intermediate states. The expected answer is the last highlighted code
+//! span (`` `*42*` ``) of the part that follows an example input, and the
+//! example is the last such input before it.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Written into a generated fixture when no answer was found for a part.
+pub const MISSING: &str = "missing";
+
+/// Hand-edited fixtures carrying `pinned: true` are never regenerated.
+pub const PINNED: &str = "pinned";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub answer: String,
+}
+
+/// One entry per part found in the text, `None` if the part has no
+/// highlighted answer with a fenced block before it.
+pub fn extract(md: &str) -> Vec<Option<Example>> {
+    let mut parts = vec![];
+    let mut block: Option<String> = None;
+    let mut last_block: Option<String> = None;
+    let mut found: Option<Example> = None;
+    let mut in_part = true;
+    let mut intro = false;
+
+    for line in md.lines() {
+        if let Some(b) = block.as_mut() {
+            if line.starts_with("```") {
+                let b = block.take().unwrap();
+                if intro {
+                    last_block = Some(b.trim_end_matches('\n').to_string());
+                }
+            } else {
+                b.push_str(line);
+                b.push('\n');
+            }
+            continue;
+        }
+        if line.starts_with("```") {
+            block = Some(String::new());
+            continue;
+        }
+        if line.contains("--- Part Two ---") {
+            in_part = true;
+            last_block = None;
+            continue;
+        }
+        if !in_part {
+            continue;
+        }
+        if !line.trim().is_empty() {
+            intro = introduces_example(line);
+        }
+        if line.starts_with("Answer:") || line.starts_with("Your puzzle answer was") {
+            parts.push(found.take());
+            last_block = None;
+            in_part = false;
+            continue;
+        }
+        if let (Some(answer), Some(input)) = (last_highlight(line), last_block.as_ref()) {
+            found = Some(Example {
+                input: input.clone(),
+                answer,
+            });
+        }
+    }
+    parts
+}
+
+/// Whether the last sentence of `para` introduces an example input, as in
+/// "For example:" or "Here's a second example:" but not "In this example,".
+fn introduces_example(para: &str) -> bool {
+    let last = para
+        .rsplit(['.', '!', '?'])
+        .find(|s| !s.trim().is_empty())
+        .unwrap_or(para)
+        .to_lowercase();
+    let words: Vec<&str> = last
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .collect();
+    words.iter().enumerate().any(|(i, w)| {
+        *w == "example" && (i >= 1 && is_intro(words[i - 1]) || i >= 2 && is_intro(words[i - 2]))
+    })
+}
+
+fn is_intro(w: &str) -> bool {
+    matches!(w, "for" | "a" | "an" | "some" | "of")
+}
+
+fn last_highlight(line: &str) -> Option<String> {
+    line.match_indices("`*")
+        .filter_map(|(i, _)| {
+            let rest = &line[i + 2..];
+            let end = rest.find("*`")?;
+            let s = &rest[..end];
+            (!s.is_empty() && !s.contains('`')).then(|| s.to_string())
+        })
+        .last()
+}
+
+/// Fixture text for `part` (1 or 2) of a day.
+pub fn render(part: usize, example: Option<&Example>) -> String {
+    match example {
+        Some(e) => format!("part{}: {}\n---\n{}\n", part, e.answer, e.input),
+        None => format!(
+            "{}: no example answer for part {} in puzzle.md\n---\n",
+            MISSING, part
+        ),
+    }
+}
+
+/// Every `<year>/_<day>` directory under `root` with a `puzzle.md` and a
+/// `Cargo.toml`, sorted.
+pub fn days(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut days = vec![];
+    for year in fs::read_dir(root)? {
+        let year = year?.path();
+        let is_year = year
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.len() == 4 && n.bytes().all(|b| b.is_ascii_digit()));
+        if !is_year || !year.is_dir() {
+            continue;
+        }
+        for day in fs::read_dir(&year)? {
+            let day = day?.path();
+            if day.join("puzzle.md").is_file() && day.join("Cargo.toml").is_file() {
+                days.push(day);
+            }
+        }
+    }
+    days.sort();
+    Ok(days)
+}
+
+fn is_pinned(fixture: &str) -> bool {
+    let header = aoc_input::split_fixture(fixture).map_or("", |(h, _)| h);
+    aoc_input::parse_answers(header)
+        .unwrap_or_default()
+        .iter()
+        .any(|(k, v)| k == PINNED && v == "true")
+}
+
+/// Regenerates `fixtures/puzzle1.txt` and `fixtures/puzzle2.txt` next to
+/// `puzzle.md` in `day`. Returns the parts that have no answer.
+pub fn write_fixtures(day: &Path) -> io::Result<Vec<usize>> {
+    let md = fs::read_to_string(day.join("puzzle.md"))?;
+    let parts = extract(&md);
+    let dir = day.join("fixtures");
+    fs::create_dir_all(&dir)?;
+
+    let mut missing = vec![];
+    for part in 1..=2 {
+        let path = dir.join(format!("puzzle{}.txt", part));
+        let pinned = fs::read_to_string(&path).is_ok_and(|s| is_pinned(&s));
+        let example = parts.get(part - 1).and_then(Option::as_ref);
+        if pinned {
+            continue;
+        }
+        if example.is_none() {
+            missing.push(part);
+        }
+        fs::write(&path, render(part, example))?;
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MD: &str = "\
+\\--- Day 1: Test ---
+For example:
+
+```
+1
+2
+
+```
+
+In this example, the state becomes:
+
+```
+9
+
+```
+
+The intermediate value `*9*` is not it. Here's a larger example:
+
+```
+3
+4
+
+```
+
+This totals `*7*` (`3*4`).
+
+*What is it?*
+
+Your puzzle answer was `123`.
+
+\\--- Part Two ---
+----------
+
+Now `*5*` without any example.
+
+Answer:
+";
+
+    #[test]
+    fn test_extract() {
+        let parts = extract(MD);
+        assert_eq!(
+            parts,
+            vec![
+                Some(Example {
+                    input: "3\n4".to_string(),
+                    answer: "7".to_string()
+                }),
+                None
+            ]
+        );
+        assert_eq!(render(1, parts[0].as_ref()), "part1: 7\n---\n3\n4\n");
+        assert!(render(2, None).starts_with("missing: "));
+    }
+
+    /// The committed fixtures are what `extract-fixtures` would write.
+    #[test]
+    fn test_fixtures_up_to_date() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        for day in days(&root).unwrap() {
+            let parts = extract(&fs::read_to_string(day.join("puzzle.md")).unwrap());
+            for part in 1..=2 {
+                let path = day.join(format!("fixtures/puzzle{}.txt", part));
+                let committed = fs::read_to_string(&path).unwrap();
+                if !is_pinned(&committed) {
+                    let example = parts.get(part - 1).and_then(Option::as_ref);
+                    assert_eq!(
+                        committed,
+                        render(part, example),
+                        "{} is stale, rerun extract-fixtures",
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Puzzle input loading shared by the days' `main`s.
+//!
+//! The input comes from, in order of precedence:
+//!
+//! - `--input <path>`, or `--input -` / a bare `-` for stdin,
+//! - `--example <n>`, the input of `fixtures/example.txt` (`n` = 1) or
+//!   `fixtures/example<n>.txt` of the day,
+//! - `input` next to the day's `Cargo.toml`.
+//!
+//! Other arguments are left to the day. Line endings are normalised to `\n`
+//! and trailing blank lines dropped before the solvers see the text.
+//!
+//! A day's corpus is its `input` plus any `inputs/<name>.txt`; the answers to
+//! `input` and `inputs/<name>.txt` live in `answers/input.toml` and
+//! `answers/<name>.toml`. Examples carry theirs in the fixture header.
+
+use std::{
+    env, fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Loads the input of the calling crate according to the command line.
+#[macro_export]
+macro_rules! input {
+    () => {
+        $crate::Input::from_args(env!("CARGO_MANIFEST_DIR"))
+    };
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Example(usize),
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(p) => write!(f, "{}", p.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Example(n) => write!(f, "example {}", n),
+        }
+    }
+}
+
+impl Source {
+    /// The source selected by `args` (without the program name), if any.
+    pub fn from_args<I: IntoIterator<Item = String>>(args: I) -> io::Result<Option<Source>> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidInput, msg);
+        let mut source = None;
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-" => source = Some(Source::Stdin),
+                "--input" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| invalid("--input needs a path".into()))?;
+                    source = Some(match path.as_str() {
+                        "-" => Source::Stdin,
+                        _ => Source::File(path.into()),
+                    });
+                }
+                "--example" => {
+                    let n = args
+                        .next()
+                        .ok_or_else(|| invalid("--example needs a number".into()))?;
+                    let n = n
+                        .parse()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| invalid(format!("Bad example number {:?}", n)))?;
+                    source = Some(Source::Example(n));
+                }
+                _ => {}
+            }
+        }
+        Ok(source)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    pub source: Source,
+    text: String,
+    /// `(key, value)` pairs: the answers keyed `part1` and `part2`, and any
+    /// parameters the day's puzzle needs alongside the input.
+    expected: Vec<(String, String)>,
+}
+
+impl Input {
+    /// Resolves the source from the process arguments, defaulting to
+    /// `<manifest_dir>/input`, and reads it.
+    pub fn from_args(manifest_dir: &str) -> io::Result<Input> {
+        let dir = Path::new(manifest_dir);
+        let source = Source::from_args(env::args().skip(1))?
+            .unwrap_or_else(|| Source::File(dir.join("input")));
+        Input::load(source, dir)
+    }
+
+    /// Reads `source`; examples are looked up in `manifest_dir/fixtures`.
+    pub fn load(source: Source, manifest_dir: &Path) -> io::Result<Input> {
+        let context = |e: io::Error, what: &dyn fmt::Display| {
+            io::Error::new(e.kind(), format!("Cannot read {}: {}", what, e))
+        };
+        let bad = |e: String, what: &dyn fmt::Display| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", what, e))
+        };
+        let mut expected = vec![];
+        let raw = match &source {
+            Source::File(p) => {
+                if let Some(answers) = answers_path(p, manifest_dir) {
+                    if let Ok(s) = fs::read_to_string(&answers) {
+                        expected = parse_answers(&s).map_err(|e| bad(e, &answers.display()))?;
+                    }
+                }
+                fs::read_to_string(p).map_err(|e| context(e, &p.display()))?
+            }
+            Source::Stdin => {
+                let mut s = String::new();
+                io::stdin()
+                    .read_to_string(&mut s)
+                    .map_err(|e| context(e, &source))?;
+                s
+            }
+            Source::Example(n) => {
+                let name = match n {
+                    1 => "example.txt".to_string(),
+                    n => format!("example{}.txt", n),
+                };
+                let path = manifest_dir.join("fixtures").join(name);
+                let s = fs::read_to_string(&path).map_err(|e| context(e, &path.display()))?;
+                let (header, input) = split_fixture(&s).unwrap_or(("", &s));
+                expected = parse_answers(header).map_err(|e| bad(e, &path.display()))?;
+                input.to_string()
+            }
+        };
+        Ok(Input {
+            source,
+            text: normalise(&raw),
+            expected,
+        })
+    }
+
+    /// The known answer to `part` (1 or 2) of this input.
+    pub fn expected(&self, part: usize) -> Option<&str> {
+        self.param(&format!("part{}", part))
+    }
+
+    /// Another value given with the input, such as the size of a grid that
+    /// differs between the examples and the puzzle.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.expected
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> Vec<&str> {
+        self.text.lines().collect()
+    }
+}
+
+/// `s` with `\n` line endings and no trailing blank lines.
+pub fn normalise(s: &str) -> String {
+    let mut lines: Vec<&str> = s
+        .split('\n')
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .collect();
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Where the answers to the corpus file `path` are kept, if it is one.
+fn answers_path(path: &Path, manifest_dir: &Path) -> Option<PathBuf> {
+    let dir = manifest_dir.canonicalize().ok()?;
+    let path = path.canonicalize().ok()?;
+    let stem = path.file_stem()?;
+    let in_corpus = match path.extension() {
+        None => path == dir.join("input"),
+        Some(ext) => ext == "txt" && path.parent()? == dir.join("inputs"),
+    };
+    in_corpus.then(|| dir.join("answers").join(stem).with_extension("toml"))
+}
+
+/// All `(name, path)` inputs of the day in `manifest_dir`: `input` first,
+/// then `inputs/*.txt` by name.
+pub fn corpus(manifest_dir: &Path) -> io::Result<Vec<(String, PathBuf)>> {
+    let mut inputs = vec![];
+    let default = manifest_dir.join("input");
+    if default.is_file() {
+        inputs.push(("input".to_string(), default));
+    }
+    let mut extra = vec![];
+    if let Ok(entries) = fs::read_dir(manifest_dir.join("inputs")) {
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|e| e == "txt") {
+                let name = path.file_stem().unwrap().to_string_lossy().to_string();
+                extra.push((name, path));
+            }
+        }
+    }
+    extra.sort();
+    inputs.extend(extra);
+    Ok(inputs)
+}
+
+/// `key = value` or `key: value` lines; values may be quoted and `#` starts
+/// a comment line. Covers both `answers.toml` and fixture headers, for this
+/// crate and `aoc-fixture` alike.
+pub fn parse_answers(s: &str) -> Result<Vec<(String, String)>, String> {
+    s.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| {
+            let (k, v) = l
+                .split_once(['=', ':'])
+                .ok_or_else(|| format!("{:?} is not key: value", l))?;
+            let v = v.trim();
+            let v = v
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(v);
+            Ok((k.trim().to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// A fixture's header and the input after its `---` line, or `None` if it
+/// has none. Either line ending ends the `---` line.
+pub fn split_fixture(s: &str) -> Option<(&str, &str)> {
+    let mut offset = 0;
+    for line in s.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim_end() == "---" {
+            return Some((&s[..offset - line.len()], &s[offset..]));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> io::Result<Option<Source>> {
+        Source::from_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_from_args() {
+        assert_eq!(args("").unwrap(), None);
+        assert_eq!(args("--dot").unwrap(), None);
+        assert_eq!(args("-").unwrap(), Some(Source::Stdin));
+        assert_eq!(args("--input -").unwrap(), Some(Source::Stdin));
+        assert_eq!(
+            args("--render --input a/b.txt").unwrap(),
+            Some(Source::File("a/b.txt".into()))
+        );
+        assert_eq!(args("--example 2").unwrap(), Some(Source::Example(2)));
+        assert!(args("--example 0").is_err());
+        assert!(args("--input").is_err());
+    }
+
+    #[test]
+    fn test_normalise() {
+        assert_eq!(normalise("a\r\nb\r\n\r\n\n"), "a\nb");
+        assert_eq!(normalise("a\n\nb\n"), "a\n\nb");
+        assert_eq!(normalise(""), "");
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let e = Input::load(Source::File(dir.join("missing")), dir).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        assert!(e.to_string().contains("missing"));
+
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../2024/_1");
+        let input = Input::load(Source::Example(1), &dir).unwrap();
+        assert_eq!(input.lines()[0], "3   4");
+        assert_eq!(input.expected(1), Some("11"));
+
+        let dir = dir.join("../_18");
+        let input = Input::load(Source::Example(1), &dir).unwrap();
+        assert_eq!(input.param("size"), Some("7"));
+        assert_eq!(input.param("bytes"), Some("12"));
+        assert_eq!(input.param("seed"), None);
+    }
+
+    #[test]
+    fn test_corpus() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../2024/_2");
+        let corpus = corpus(&dir).unwrap();
+        let names: Vec<&str> = corpus.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["input", "edges"]);
+
+        let input = Input::load(Source::File(corpus[1].1.clone()), &dir).unwrap();
+        assert_eq!(input.lines()[0], "1 2");
+        assert_eq!(input.expected(1), Some("3"));
+        let input = Input::load(Source::File(dir.join("input")), &dir).unwrap();
+        assert!(input.expected(2).is_some());
+        let elsewhere = dir.join("fixtures/example.txt");
+        let input = Input::load(Source::File(elsewhere), &dir).unwrap();
+        assert_eq!(input.expected(1), None);
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let a = parse_answers("# real input\npart1 = 42\npart2 = \"a,b\"\n");
+        assert_eq!(
+            a,
+            Ok(vec![
+                ("part1".to_string(), "42".to_string()),
+                ("part2".to_string(), "a,b".to_string())
+            ])
+        );
+        assert_eq!(
+            parse_answers("size: 7\r\n"),
+            Ok(vec![("size".to_string(), "7".to_string())])
+        );
+        assert!(parse_answers("part1 42").is_err());
+    }
+
+    #[test]
+    fn test_split_fixture() {
+        assert_eq!(
+            split_fixture("part1: 1\n---\nx\ny\n"),
+            Some(("part1: 1\n", "x\ny\n"))
+        );
+        assert_eq!(
+            split_fixture("a: 1\r\n---\r\nx\r\n"),
+            Some(("a: 1\r\n", "x\r\n"))
+        );
+        assert_eq!(split_fixture("---\n"), Some(("", "")));
+        assert_eq!(split_fixture("x\n--\n"), None);
+    }
+}
@@ -0,0 +1,269 @@
+//! Integer number theory and decimal digit tricks shared between days.
+//!
+//! [`gcd`], [`lcm`] and [`range_sum`] work on any primitive integer. The
+//! modular functions work in `i128`, wide enough that products of two puzzle
+//! numbers never overflow, and return `None` where an answer would not fit.
+//! [`checked_mul_add`] and [`checked_det`] are the `i128` building blocks for
+//! callers chaining more products than that.
+//! Digit functions work on `u64` through `ilog10` rather than strings.
+
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// The primitive integers.
+pub trait Int:
+    Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TWO: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! int {
+    ($abs:expr => $($t:ty),*) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const TWO: Self = 2;
+
+            fn abs(self) -> Self {
+                $abs(self)
+            }
+        }
+    )*};
+}
+
+int!(|n: Self| n.abs() => i8, i16, i32, i64, i128, isize);
+int!(|n: Self| n => u8, u16, u32, u64, u128, usize);
+
+/// Greatest common divisor, never negative. `gcd(0, 0)` is 0.
+pub fn gcd<T: Int>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, never negative. Zero if either argument is.
+pub fn lcm<T: Int>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    (a / gcd(a, b) * b).abs()
+}
+
+/// `lo + (lo + 1) + ... + hi`, or zero if `hi < lo`. Halves whichever
+/// factor is even first, so it only overflows if the sum does.
+pub fn range_sum<T: Int>(lo: T, hi: T) -> T {
+    if hi < lo {
+        return T::ZERO;
+    }
+    let (n, ends) = (hi - lo + T::ONE, lo + hi);
+    match n % T::TWO == T::ZERO {
+        true => n / T::TWO * ends,
+        false => ends / T::TWO * n,
+    }
+}
+
+/// Returns `(g, x, y)` with `a * x + b * y == g == gcd(a, b)`.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    match r0 < 0 {
+        true => (-r0, -x0, -y0),
+        false => (r0, x0, y0),
+    }
+}
+
+/// `a * b` modulo a positive `m`, in `0..m`, without overflowing for any
+/// `m` up to `i128::MAX / 2`.
+pub fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    assert!(m > 0, "Modulus must be positive");
+    let (mut a, mut b) = (a.rem_euclid(m), b.rem_euclid(m));
+    if let Some(p) = a.checked_mul(b) {
+        return p % m;
+    }
+    let mut res = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            res = (res + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    res
+}
+
+/// `x` with `a * x ≡ 1 (mod m)`, in `0..m`, if `a` and `m` are coprime.
+pub fn mod_inv(a: i128, m: i128) -> Option<i128> {
+    assert!(m > 0, "Modulus must be positive");
+    let (g, x, _) = ext_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Chinese remainder theorem: the `x` in `0..l` satisfying every
+/// `x ≡ r (mod m)` of `congruences`, together with `l`, the lcm of the
+/// moduli. Moduli need not be coprime. `None` if the congruences conflict
+/// or `l` overflows.
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let (mut x, mut l) = (0, 1);
+    for &(r, m) in congruences {
+        assert!(m > 0, "Modulus must be positive");
+        let g = gcd(l, m);
+        let diff = r - x;
+        if diff % g != 0 {
+            return None;
+        }
+        // x + l * t ≡ r (mod m) reduces to (l / g) * t ≡ diff / g (mod m / g).
+        let m_g = m / g;
+        let t = mul_mod(diff / g, mod_inv(l / g, m_g)?, m_g);
+        let next = (l / g).checked_mul(m)?;
+        x = (x + mul_mod(l, t, next)).rem_euclid(next);
+        l = next;
+    }
+    Some((x, l))
+}
+
+/// Where `start` ends up after `n` steps of `step` on a ring of `m`
+/// positions, in `0..m`.
+pub fn advance_mod(start: i128, step: i128, n: i128, m: i128) -> i128 {
+    (start.rem_euclid(m) + mul_mod(step, n, m)) % m
+}
+
+/// `a * b + c`, or `None` on overflow.
+pub fn checked_mul_add(a: i128, b: i128, c: i128) -> Option<i128> {
+    a.checked_mul(b)?.checked_add(c)
+}
+
+/// `a * d - b * c`, the determinant of `[[a, b], [c, d]]`, or `None` on
+/// overflow.
+pub fn checked_det(a: i128, b: i128, c: i128, d: i128) -> Option<i128> {
+    a.checked_mul(d)?.checked_sub(b.checked_mul(c)?)
+}
+
+/// `10^k`. Panics past `10^19`.
+pub fn pow10(k: u32) -> u64 {
+    10u64.pow(k)
+}
+
+/// Decimal digits of `n`; 0 has one.
+pub fn digits(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Splits off the last `k` digits: `split_digits(123456, 2) == (1234, 56)`.
+pub fn split_digits(n: u64, k: u32) -> (u64, u64) {
+    let p = pow10(k);
+    (n / p, n % p)
+}
+
+/// The digits of `a` followed by those of `b`, or `None` on overflow.
+pub fn checked_concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(10u64.checked_pow(digits(b))?)?.checked_add(b)
+}
+
+/// The digits of `a` followed by those of `b`. Panics on overflow.
+pub fn concat(a: u64, b: u64) -> u64 {
+    checked_concat(a, b).expect("Concatenation overflows u64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0usize, 7), 7);
+        assert_eq!(gcd(0i32, 0), 0);
+        assert_eq!(lcm(4u32, 6), 12);
+        assert_eq!(lcm(-4i64, 6), 12);
+        assert_eq!(lcm(101isize, 103), 10403);
+        assert_eq!(lcm(0u8, 3), 0);
+    }
+
+    #[test]
+    fn test_range_sum() {
+        assert_eq!(range_sum(4usize, 4), 4);
+        assert_eq!(range_sum(1u64, 100), 5050);
+        assert_eq!(range_sum(2u32, 5), 14);
+        assert_eq!(range_sum(-3i32, 5), 9);
+        assert_eq!(range_sum(5u8, 4), 0);
+        // The naive (n * (lo + hi)) / 2 would overflow here.
+        assert_eq!(range_sum(0u64, 1 << 32), (1 << 31) * ((1 << 32) + 1));
+    }
+
+    #[test]
+    fn test_ext_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (7, 0), (0, -7), (94, 22)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "{} {}", a, b);
+            assert_eq!(a * x + b * y, g, "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_modular() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(-3, 11), Some(7));
+        assert_eq!(mod_inv(6, 9), None);
+        assert_eq!(mul_mod(-1, 5, 7), 2);
+        let big = i128::MAX / 2;
+        assert_eq!(mul_mod(big - 1, 2, big), big - 2);
+        assert_eq!(advance_mod(2, -3, 5, 11), 9);
+        // 2^100 ≡ 1 (mod 3).
+        assert_eq!(advance_mod(0, 1, 1 << 100, 3), 1);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(-1, 101), (-1, 103)]), Some((10402, 10403)));
+        assert_eq!(crt(&[(0, 1 << 100), (1, (1 << 100) + 1)]), None);
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(checked_mul_add(6, 7, -2), Some(40));
+        assert_eq!(checked_mul_add(i128::MAX, 2, 0), None);
+        assert_eq!(checked_mul_add(i128::MAX, 1, 1), None);
+        assert_eq!(checked_det(94, 22, 34, 67), Some(94 * 67 - 22 * 34));
+        assert_eq!(checked_det(1, 2, 2, 4), Some(0));
+        let big = 1 << 100;
+        assert_eq!(checked_det(big, big, big, big), None);
+        assert_eq!(checked_det(big, 0, 0, 1), Some(big));
+    }
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(digits(0), 1);
+        assert_eq!(digits(9), 1);
+        assert_eq!(digits(10), 2);
+        assert_eq!(digits(u64::MAX), 20);
+        assert_eq!(split_digits(123456, 2), (1234, 56));
+        assert_eq!(split_digits(1000, 2), (10, 0));
+        assert_eq!(concat(12, 345), 12345);
+        assert_eq!(concat(12, 0), 120);
+        assert_eq!(checked_concat(u64::MAX / 10, 99), None);
+        assert_eq!(checked_concat(1, u64::MAX), None);
+    }
+}
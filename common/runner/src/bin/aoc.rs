@@ -0,0 +1,358 @@
+//! Workspace runner.
+//!
+//! `aoc run [YEAR [DAY]] [--timeout SECS] [--json PATH] [--junit PATH] [ARGS...]`
+//! builds the selected days, runs each on every input of its corpus with the
+//! per-part budget, prints a line per part and writes the reports. Other
+//! arguments are passed on to every day; `--input` or `--example` among them
+//! replace the corpus.
+//!
+//! `aoc gen YEAR DAY [--size N] [--seed S]` prints a synthetic input for the
+//! day, so that `aoc gen 2024 20 --size 1000 | aoc run 2024 20 --input -`
+//! stress-tests it.
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{self, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_runner::{
+    report::{self, DayReport},
+    PartReport, Status, DEFAULT_TIMEOUT,
+};
+
+struct Options {
+    year: Option<u32>,
+    day: Option<u32>,
+    budget: Duration,
+    json: Option<PathBuf>,
+    junit: Option<PathBuf>,
+    /// Arguments for the days.
+    rest: Vec<String>,
+}
+
+fn usage() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "Usage: aoc run [YEAR [DAY]] [--timeout SECS] [--json PATH] [--junit PATH] [ARGS...]\n       \
+         aoc gen YEAR DAY [--size N] [--seed S]",
+    )
+}
+
+fn parse_options(args: &[String]) -> io::Result<Options> {
+    let mut opts = Options {
+        year: None,
+        day: None,
+        budget: DEFAULT_TIMEOUT,
+        json: None,
+        junit: None,
+        rest: vec![],
+    };
+    let mut args = args.iter().peekable();
+    opts.year = args
+        .next_if(|a| a.parse::<u32>().is_ok())
+        .map(|a| a.parse().unwrap());
+    if opts.year.is_some() {
+        opts.day = args
+            .next_if(|a| a.parse::<u32>().is_ok())
+            .map(|a| a.parse().unwrap());
+    }
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--timeout" => {
+                let secs = args
+                    .next()
+                    .and_then(|s| s.parse::<f64>().ok())
+                    .filter(|s| *s > 0.0)
+                    .ok_or_else(usage)?;
+                opts.budget = Duration::from_secs_f64(secs);
+            }
+            "--json" => opts.json = Some(args.next().ok_or_else(usage)?.into()),
+            "--junit" => opts.junit = Some(args.next().ok_or_else(usage)?.into()),
+            _ => opts.rest.push(arg.clone()),
+        }
+    }
+    Ok(opts)
+}
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// `(year, day)` of every day crate, sorted.
+fn days(root: &Path) -> io::Result<Vec<(u32, u32)>> {
+    let mut days = vec![];
+    for year in fs::read_dir(root)? {
+        let year = year?.path();
+        let Some(y) = year
+            .file_name()
+            .and_then(|n| n.to_str())
+            .filter(|n| n.len() == 4)
+            .and_then(|n| n.parse().ok())
+        else {
+            continue;
+        };
+        for day in fs::read_dir(&year)? {
+            let day = day?.path();
+            let d = day
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_prefix('_'))
+                .and_then(|n| n.parse().ok());
+            if let Some(d) = d.filter(|_| day.join("Cargo.toml").is_file()) {
+                days.push((y, d));
+            }
+        }
+    }
+    days.sort();
+    Ok(days)
+}
+
+fn package(year: u32, day: u32) -> String {
+    format!("_{}_{}", year, day)
+}
+
+fn build(root: &Path, days: &[(u32, u32)]) -> io::Result<()> {
+    let mut cmd = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cmd.current_dir(root).args(["build", "--release", "-q"]);
+    for (y, d) in days {
+        cmd.args(["-p", &package(*y, *d)]);
+    }
+    if cmd.status()?.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other("Build failed"))
+    }
+}
+
+fn binary(root: &Path, year: u32, day: u32) -> PathBuf {
+    env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join("target"))
+        .join("release")
+        .join(package(year, day))
+}
+
+/// Runs one day's binary on `input` (a corpus name and path) and reads back
+/// its report. Parts the day never reported, because it crashed or hung
+/// outside a part, are filled in.
+fn run_day(
+    root: &Path,
+    (year, day): (u32, u32),
+    input: (&str, Option<&Path>),
+    opts: &Options,
+) -> io::Result<DayReport> {
+    let report_path = env::temp_dir().join(format!("aoc-{}-{}-{}.tsv", process::id(), year, day));
+    let _ = fs::remove_file(&report_path);
+
+    let mut cmd = Command::new(binary(root, year, day));
+    if let Some(path) = input.1 {
+        cmd.arg("--input").arg(path);
+    }
+    let mut child = cmd
+        .current_dir(root)
+        .arg("--report")
+        .arg(&report_path)
+        .arg("--timeout")
+        .arg(opts.budget.as_secs_f64().to_string())
+        .args(&opts.rest)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stderr = child.stderr.take().unwrap();
+    let errors = thread::spawn(move || {
+        let mut s = String::new();
+        let _ = stderr.read_to_string(&mut s);
+        s
+    });
+
+    // Both parts plus whatever else the day prints.
+    let deadline = Instant::now() + opts.budget * 3;
+    let exit: Option<ExitStatus> = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if Instant::now() > deadline {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    let errors = errors.join().unwrap_or_default();
+
+    let mut parts: Vec<PartReport> = fs::read_to_string(&report_path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(report::decode)
+        .collect();
+    let _ = fs::remove_file(&report_path);
+    for part in 1..=2 {
+        if parts.iter().any(|p| p.part == part) {
+            continue;
+        }
+        let (status, message) = match exit {
+            None => (
+                Status::Timeout,
+                format!("Killed after {:.1?}", opts.budget * 3),
+            ),
+            Some(exit) => {
+                let tail = errors.lines().rev().take(5).collect::<Vec<_>>();
+                let tail = tail.into_iter().rev().collect::<Vec<_>>().join("\n");
+                let status = match errors.contains("panicked") {
+                    true => Status::Panic,
+                    false => Status::Error,
+                };
+                (status, format!("Exited with {}: {}", exit, tail))
+            }
+        };
+        parts.push(PartReport {
+            part,
+            status,
+            answer: None,
+            expected: None,
+            duration: Duration::ZERO,
+            message: Some(message),
+        });
+    }
+    parts.sort_by_key(|p| p.part);
+    Ok(DayReport {
+        year,
+        day,
+        input: input.0.to_string(),
+        parts,
+    })
+}
+
+fn run(args: &[String]) -> io::Result<bool> {
+    let opts = parse_options(args)?;
+    let root = root();
+    let selected: Vec<(u32, u32)> = days(&root)?
+        .into_iter()
+        .filter(|(y, d)| opts.year.is_none_or(|o| o == *y) && opts.day.is_none_or(|o| o == *d))
+        .collect();
+    if selected.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "No such day"));
+    }
+    build(&root, &selected)?;
+
+    // An input chosen on the command line replaces the corpus.
+    let chosen = opts
+        .rest
+        .iter()
+        .position(|a| matches!(a.as_str(), "-" | "--input" | "--example"))
+        .map(|i| {
+            opts.rest[i..]
+                .iter()
+                .take(2)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+
+    let mut reports = vec![];
+    for (year, day) in selected {
+        let corpus = match &chosen {
+            Some(label) => vec![(label.clone(), None)],
+            None => {
+                let dir = root.join(year.to_string()).join(format!("_{}", day));
+                let corpus = aoc_input::corpus(&dir)?;
+                match corpus.is_empty() {
+                    // Let the day report the missing input.
+                    true => vec![("input".to_string(), None)],
+                    false => corpus.into_iter().map(|(n, p)| (n, Some(p))).collect(),
+                }
+            }
+        };
+        for (name, path) in corpus.iter() {
+            let r = run_day(&root, (year, day), (name, path.as_deref()), &opts)?;
+            for p in r.parts.iter() {
+                println!(
+                    "{:<9} {:<12} part{} {:<7} {:>9.3}s  {}",
+                    r.name(),
+                    r.input,
+                    p.part,
+                    p.status.to_string(),
+                    p.duration.as_secs_f64(),
+                    detail(p)
+                );
+            }
+            reports.push(r);
+        }
+    }
+
+    let failed: Vec<(&DayReport, &PartReport)> = reports
+        .iter()
+        .flat_map(|r| r.parts.iter().map(move |p| (r, p)))
+        .filter(|(_, p)| p.status != Status::Ok)
+        .collect();
+    if !failed.is_empty() {
+        println!("\nDiverging:");
+        for (r, p) in failed.iter() {
+            println!(
+                "  {} {} part{}: {} {}",
+                r.name(),
+                r.input,
+                p.part,
+                p.status,
+                detail(p)
+            );
+        }
+    }
+
+    if let Some(path) = &opts.json {
+        fs::write(path, report::to_json(&reports))?;
+    }
+    if let Some(path) = &opts.junit {
+        fs::write(path, report::to_junit(&reports))?;
+    }
+    Ok(failed.is_empty())
+}
+
+/// Builds the day and lets it print its generated input.
+fn generate(args: &[String]) -> io::Result<bool> {
+    let [year, day, rest @ ..] = args else {
+        return Err(usage());
+    };
+    let year: u32 = year.parse().map_err(|_| usage())?;
+    let day: u32 = day.parse().map_err(|_| usage())?;
+    let root = root();
+    if !days(&root)?.contains(&(year, day)) {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "No such day"));
+    }
+    build(&root, &[(year, day)])?;
+    let status = Command::new(binary(&root, year, day))
+        .current_dir(&root)
+        .arg("--gen")
+        .args(rest)
+        .status()?;
+    Ok(status.success())
+}
+
+fn detail(p: &PartReport) -> String {
+    match p.status {
+        Status::Wrong => format!(
+            "{} (expected {})",
+            p.answer.as_deref().unwrap_or_default(),
+            p.expected.as_deref().unwrap_or_default()
+        ),
+        Status::Ok => p.answer.clone().unwrap_or_default(),
+        _ => p.message.clone().unwrap_or_default(),
+    }
+}
+
+fn main() -> io::Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let ok = match args.first().map(String::as_str) {
+        Some("run") => run(&args[1..])?,
+        Some("gen") => generate(&args[1..])?,
+        _ => return Err(usage()),
+    };
+    if !ok {
+        process::exit(1);
+    }
+    Ok(())
+}
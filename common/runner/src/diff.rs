@@ -0,0 +1,271 @@
+//! Differential testing of a day's alternative implementations.
+//!
+//! A day registers the competing implementations of a computation as a
+//! slice of [`Variant`]s. [`Check`] feeds them generated inputs and, on the
+//! first input where their outcomes differ, shrinks it by deleting lines and
+//! characters and simplifying digits and letters for as long as they still
+//! disagree.
+
+use std::{
+    env,
+    fmt::{self, Debug, Display},
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{panic_message, rng::Rng};
+
+/// A named implementation. The first of a day's variants is its default.
+pub type Variant<F> = (&'static str, F);
+
+/// What one variant made of an input: its result, or its panic message.
+pub type Outcome<O> = Result<O, String>;
+
+/// Inputs tried while shrinking before settling for the smallest so far.
+const SHRINK_BUDGET: usize = 5000;
+
+pub struct Check<'a, F> {
+    variants: &'a [Variant<F>],
+    cases: usize,
+    seed: u64,
+    valid: fn(&str) -> bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Disagreement<O> {
+    pub seed: u64,
+    /// Index of the generated case that first disagreed.
+    pub case: usize,
+    pub generated: String,
+    /// `generated` shrunk to a minimal input that still disagrees.
+    pub input: String,
+    pub outcomes: Vec<(&'static str, Outcome<O>)>,
+}
+
+impl<O: Debug> Display for Disagreement<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Variants disagree on case {} of seed {} ({} bytes), shrunk to:",
+            self.case,
+            self.seed,
+            self.generated.len()
+        )?;
+        for line in self.input.lines() {
+            writeln!(f, "    {}", line)?;
+        }
+        for (name, outcome) in self.outcomes.iter() {
+            match outcome {
+                Ok(o) => writeln!(f, "  {}: {:?}", name, o)?,
+                Err(e) => writeln!(f, "  {}: panicked: {}", name, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn same<O: PartialEq>(a: &Outcome<O>, b: &Outcome<O>) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+impl<'a, F> Check<'a, F> {
+    /// 200 cases from seed `$AOC_SEED`, or 0.
+    pub fn new(variants: &'a [Variant<F>]) -> Check<'a, F> {
+        assert!(variants.len() >= 2, "Nothing to compare");
+        Check {
+            variants,
+            cases: 200,
+            seed: env::var("AOC_SEED")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0),
+            valid: |_| true,
+        }
+    }
+
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Restricts shrinking to inputs the day accepts. Generated inputs are
+    /// assumed valid.
+    pub fn valid(mut self, valid: fn(&str) -> bool) -> Self {
+        self.valid = valid;
+        self
+    }
+
+    fn outcomes<O>(
+        &self,
+        run: &impl Fn(&F, &str) -> O,
+        input: &str,
+    ) -> Vec<(&'static str, Outcome<O>)> {
+        self.variants
+            .iter()
+            .map(|(name, f)| {
+                let res = panic::catch_unwind(AssertUnwindSafe(|| run(f, input)));
+                (*name, res.map_err(panic_message))
+            })
+            .collect()
+    }
+
+    fn disagree<O: PartialEq>(&self, run: &impl Fn(&F, &str) -> O, input: &str) -> bool {
+        let outcomes = self.outcomes(run, input);
+        outcomes.iter().any(|(_, o)| !same(o, &outcomes[0].1))
+    }
+
+    /// Runs every variant through `run` on each generated case. Case `i`
+    /// draws from `Rng::new(seed + i)`, so it can be replayed on its own.
+    pub fn first_disagreement<O, R, G>(&self, run: R, gen: G) -> Option<Disagreement<O>>
+    where
+        O: PartialEq,
+        R: Fn(&F, &str) -> O,
+        G: Fn(&mut Rng) -> String,
+    {
+        let (case, generated) = (0..self.cases).find_map(|case| {
+            let input = gen(&mut Rng::new(self.seed.wrapping_add(case as u64)));
+            self.disagree(&run, &input).then_some((case, input))
+        })?;
+
+        let mut input = generated.clone();
+        let mut budget = SHRINK_BUDGET;
+        'shrink: while budget > 0 {
+            for candidate in shrink(&input) {
+                if budget == 0 {
+                    break 'shrink;
+                }
+                budget -= 1;
+                if (self.valid)(&candidate) && self.disagree(&run, &candidate) {
+                    input = candidate;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+
+        Some(Disagreement {
+            seed: self.seed,
+            case,
+            outcomes: self.outcomes(&run, &input),
+            generated,
+            input,
+        })
+    }
+
+    /// Panics with the shrunk input if any case disagrees.
+    pub fn assert_agree<O, R, G>(&self, run: R, gen: G)
+    where
+        O: PartialEq + Debug,
+        R: Fn(&F, &str) -> O,
+        G: Fn(&mut Rng) -> String,
+    {
+        if let Some(d) = self.first_disagreement(run, gen) {
+            panic!("{}", d);
+        }
+    }
+}
+
+/// Every way of removing `k` consecutive items, halving `k` from `n / 2`.
+fn removals<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut out = vec![];
+    let mut k = (items.len() / 2).max(1);
+    while k > 0 && k <= items.len() {
+        for start in (0..=items.len() - k).step_by(k) {
+            out.push([&items[..start], &items[start + k..]].concat());
+        }
+        k /= 2;
+    }
+    out
+}
+
+/// Smaller inputs to try, roughly largest step first: fewer lines, fewer
+/// characters, then lower digits and earlier letters.
+fn shrink(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut out: Vec<String> = removals(&lines).into_iter().map(|l| l.join("\n")).collect();
+
+    let chars: Vec<char> = input.chars().collect();
+    out.extend(removals(&chars).into_iter().map(String::from_iter));
+    for (i, c) in chars.iter().enumerate() {
+        let simpler = match c {
+            '1'..='9' => vec!['0', (*c as u8 - 1) as char],
+            'b'..='z' => vec!['a'],
+            'B'..='Z' => vec!['A'],
+            _ => vec![],
+        };
+        for s in simpler {
+            let mut chars = chars.clone();
+            chars[i] = s;
+            out.push(chars.into_iter().collect());
+        }
+    }
+    out.dedup();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sum(s: &str) -> u32 {
+        s.chars().filter_map(|c| c.to_digit(10)).sum()
+    }
+
+    /// Stops after the first 7.
+    fn sum_buggy(s: &str) -> u32 {
+        let mut sum = 0;
+        for d in s.chars().filter_map(|c| c.to_digit(10)) {
+            sum += d;
+            if d == 7 {
+                break;
+            }
+        }
+        sum
+    }
+
+    type Sum = fn(&str) -> u32;
+
+    const SUMS: &[Variant<Sum>] = &[("sum", sum), ("buggy", sum_buggy)];
+
+    fn digits(r: &mut Rng) -> String {
+        let n = r.range(1, 12);
+        r.string("0123456789x", n)
+    }
+
+    #[test]
+    fn test_shrinks_to_minimal() {
+        let d = Check::new(SUMS)
+            .seed(3)
+            .first_disagreement(|f, s| f(s), digits)
+            .unwrap();
+        assert_eq!(d.input, "71");
+        assert_eq!(d.outcomes, vec![("sum", Ok(8)), ("buggy", Ok(7))]);
+        assert!(d.to_string().contains("shrunk to:\n    71\n"));
+    }
+
+    #[test]
+    fn test_agree_and_panics() {
+        Check::new(&SUMS[..1].repeat(2)).assert_agree(|f, s| f(s), digits);
+
+        let variants: &[Variant<Sum>] = &[
+            ("sum", sum),
+            ("strict", |s| {
+                s.chars().map(|c| c.to_digit(10).unwrap()).sum()
+            }),
+        ];
+        let d = Check::new(variants)
+            .valid(|s| !s.is_empty())
+            .first_disagreement(|f, s| f(s), digits)
+            .unwrap();
+        assert_eq!(d.input, "a");
+        assert!(d.outcomes[1].1.is_err());
+    }
+}
@@ -0,0 +1,499 @@
+//! Runs a day's parts in isolation and reports how each went.
+//!
+//! Every part runs on its own worker thread under a time budget, with panics
+//! caught, so one broken part never hides the others. A thread cannot be
+//! stopped, so once a part times out the day's later parts and extras are
+//! skipped rather than timed against the one still running. A day's `main` builds a
+//! [`Day`] from its [`Input`], registers its parts, hands any further output
+//! to [`Day::extras`] and calls [`Day::finish`]; the `aoc` binary runs days as
+//! subprocesses and collects their reports, which are written as each part
+//! finishes.
+//!
+//! Before loading its input, a day's `main` hands its generator to
+//! [`generate`], which takes over when the day is run with `--gen`.
+
+use std::{
+    any::Any,
+    env,
+    fmt::{self, Display},
+    fs, io,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{mpsc, Arc},
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_input::Input;
+use rng::Rng;
+
+pub mod diff;
+pub mod report;
+pub mod rng;
+
+use diff::Variant;
+
+/// Budget per part unless `--timeout` says otherwise.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// `--size` of generated inputs unless given.
+pub const DEFAULT_SIZE: usize = 100;
+
+/// Makes a synthetic input of roughly `size` units, lines or cells per side
+/// depending on the day, from the given generator.
+pub type Generator = fn(usize, &mut Rng) -> String;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// Finished with an answer other than the expected one.
+    Wrong,
+    /// The solver returned an error.
+    Error,
+    Panic,
+    Timeout,
+    /// Not run because an earlier part timed out and may still be running.
+    Skipped,
+}
+
+impl Status {
+    pub const ALL: [Status; 6] = [
+        Status::Ok,
+        Status::Wrong,
+        Status::Error,
+        Status::Panic,
+        Status::Timeout,
+        Status::Skipped,
+    ];
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Ok => "ok",
+            Status::Wrong => "wrong",
+            Status::Error => "error",
+            Status::Panic => "panic",
+            Status::Timeout => "timeout",
+            Status::Skipped => "skipped",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub part: usize,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    pub duration: Duration,
+    /// Panic or error message, or why the answer is missing.
+    pub message: Option<String>,
+}
+
+type Solver = Box<dyn FnOnce(&Input) -> Result<String, String> + Send>;
+
+/// What a worker made of its job: the result or panic payload and how long
+/// it took, or `None` if the budget ran out first.
+type Finished<T> = Option<(thread::Result<T>, Duration)>;
+
+pub struct Day {
+    input: Arc<Input>,
+    budget: Duration,
+    report: Option<PathBuf>,
+    /// First failure to write `report`, surfaced by [`Day::finish`].
+    report_error: Option<io::Error>,
+    parts: Vec<PartReport>,
+    /// `--impl` and whether any of the day's variants had that name.
+    variant: Option<(String, bool)>,
+    /// Whether a worker was left running past its budget.
+    timed_out: bool,
+}
+
+impl Day {
+    /// Takes `--timeout <secs>`, `--report <path>` and `--impl <name>` from
+    /// the process arguments, leaving the rest to the day.
+    pub fn from_args(input: Input) -> io::Result<Day> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, msg.to_string());
+        let mut day = Day::new(input, DEFAULT_TIMEOUT);
+        let mut args = env::args().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--timeout" => {
+                    let secs = args
+                        .next()
+                        .and_then(|s| s.parse::<f64>().ok())
+                        .filter(|s| *s > 0.0)
+                        .ok_or_else(|| invalid("--timeout needs a number of seconds"))?;
+                    day.budget = Duration::from_secs_f64(secs);
+                }
+                "--report" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| invalid("--report needs a path"))?;
+                    day.report = Some(path.into());
+                }
+                "--impl" => {
+                    let name = args.next().ok_or_else(|| invalid("--impl needs a name"))?;
+                    day.variant = Some((name, false));
+                }
+                _ => {}
+            }
+        }
+        Ok(day)
+    }
+
+    pub fn new(input: Input, budget: Duration) -> Day {
+        Day {
+            input: Arc::new(input),
+            budget,
+            report: None,
+            report_error: None,
+            parts: vec![],
+            variant: None,
+            timed_out: false,
+        }
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    pub fn parts(&self) -> &[PartReport] {
+        &self.parts
+    }
+
+    /// The variant named by `--impl`, or the first if the option is absent
+    /// or names none of these.
+    pub fn variant<F: Copy>(&mut self, variants: &[Variant<F>]) -> F {
+        let chosen = self.variant.as_mut().and_then(|(name, matched)| {
+            let f = variants.iter().find(|(n, _)| n == name)?;
+            *matched = true;
+            Some(f.1)
+        });
+        chosen.unwrap_or(variants[0].1)
+    }
+
+    /// Runs `part` (1 or 2) and prints its answer.
+    pub fn part<R, F>(&mut self, part: usize, f: F)
+    where
+        R: Display,
+        F: FnOnce(&Input) -> R + Send + 'static,
+    {
+        self.run(part, Box::new(move |i| Ok(f(i).to_string())));
+    }
+
+    /// Like [`Day::part`] for solvers that can fail, e.g. on bad input.
+    pub fn try_part<R, E, F>(&mut self, part: usize, f: F)
+    where
+        R: Display,
+        E: Display,
+        F: FnOnce(&Input) -> Result<R, E> + Send + 'static,
+    {
+        self.run(
+            part,
+            Box::new(move |i| f(i).map(|r| r.to_string()).map_err(|e| e.to_string())),
+        );
+    }
+
+    /// Runs `f` on a worker thread within the budget, catching panics.
+    fn spawn<T, F>(&mut self, f: F) -> Finished<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Input) -> T + Send + 'static,
+    {
+        let input = Arc::clone(&self.input);
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let start = Instant::now();
+            let res = panic::catch_unwind(AssertUnwindSafe(|| f(&input)));
+            // The receiver is gone if the job already timed out.
+            let _ = tx.send((res, start.elapsed()));
+        });
+        let finished = rx.recv_timeout(self.budget).ok();
+        self.timed_out |= finished.is_none();
+        finished
+    }
+
+    fn run(&mut self, part: usize, f: Solver) {
+        let expected = self.input.expected(part).map(String::from);
+        let mut report = PartReport {
+            part,
+            status: Status::Timeout,
+            answer: None,
+            expected,
+            duration: self.budget,
+            message: None,
+        };
+        if self.timed_out {
+            report.status = Status::Skipped;
+            report.duration = Duration::ZERO;
+            report.message = Some("An earlier part timed out".into());
+        } else {
+            match self.spawn(f) {
+                Some((res, duration)) => {
+                    report.duration = duration;
+                    match res {
+                        Ok(Ok(answer)) => {
+                            report.status = match &report.expected {
+                                Some(e) if *e != answer => Status::Wrong,
+                                _ => Status::Ok,
+                            };
+                            report.answer = Some(answer);
+                        }
+                        Ok(Err(e)) => {
+                            report.status = Status::Error;
+                            report.message = Some(e);
+                        }
+                        Err(payload) => {
+                            report.status = Status::Panic;
+                            report.message = Some(panic_message(payload));
+                        }
+                    }
+                }
+                None => {
+                    report.message = Some(format!("No answer after {:.1?}", self.budget));
+                }
+            }
+        }
+
+        println!("Part{}:", part);
+        match (&report.answer, report.status) {
+            (Some(a), Status::Wrong) => println!(
+                "{} (expected {})",
+                a,
+                report.expected.as_deref().unwrap_or_default()
+            ),
+            (Some(a), _) => println!("{}", a),
+            (None, status) => println!(
+                "{}: {}",
+                status,
+                report.message.as_deref().unwrap_or_default()
+            ),
+        }
+        self.parts.push(report);
+        self.write_report();
+    }
+
+    /// Runs a day's further output, such as renderings or statistics, under
+    /// the same budget and panic guard as its parts. Skipped under
+    /// `--report`, where nobody reads it; its failures are printed but never
+    /// fail the day.
+    pub fn extras<F>(&mut self, f: F)
+    where
+        F: FnOnce(&Input) + Send + 'static,
+    {
+        if self.report.is_some() {
+            return;
+        }
+        if self.timed_out {
+            println!("Extras skipped: an earlier part timed out");
+            return;
+        }
+        match self.spawn(f) {
+            Some((Ok(()), _)) => {}
+            Some((Err(payload), _)) => println!("Extras panicked: {}", panic_message(payload)),
+            None => println!("Extras stopped after {:.1?}", self.budget),
+        }
+    }
+
+    /// Rewrites the `--report` file with every part so far, so that a day
+    /// that is killed or crashes later still reports the parts it finished.
+    fn write_report(&mut self) {
+        let Some(path) = &self.report else {
+            return;
+        };
+        let lines: String = self
+            .parts
+            .iter()
+            .map(|p| report::encode(p) + "\n")
+            .collect();
+        if let Err(e) = fs::write(path, lines) {
+            self.report_error.get_or_insert(e);
+        }
+    }
+
+    /// Fails unless the `--report` file, if any, was written, every part is
+    /// ok and `--impl` named one of the day's variants.
+    pub fn finish(mut self) -> io::Result<()> {
+        if let Some((name, false)) = &self.variant {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("No implementation named {}", name),
+            ));
+        }
+        self.write_report();
+        if let Some(e) = self.report_error.take() {
+            return Err(e);
+        }
+        let failed: Vec<String> = self
+            .parts
+            .iter()
+            .filter(|p| p.status != Status::Ok)
+            .map(|p| format!("part {} {}", p.part, p.status))
+            .collect();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(io::Error::other(failed.join(", ")))
+        }
+    }
+}
+
+/// With `--gen` among the process arguments, prints an input made by
+/// `generator` with `--size <n>` and `--seed <n>` (default 0) and returns
+/// `true`, leaving the day nothing else to do.
+pub fn generate(generator: Generator) -> io::Result<bool> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.iter().any(|a| a == "--gen") {
+        return Ok(false);
+    }
+    let number = |flag: &str, default: u64| -> io::Result<u64> {
+        match args.iter().position(|a| a == flag) {
+            None => Ok(default),
+            Some(i) => args.get(i + 1).and_then(|n| n.parse().ok()).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} needs a number", flag),
+                )
+            }),
+        }
+    };
+    let size = number("--size", DEFAULT_SIZE as u64)? as usize;
+    let mut rng = Rng::new(number("--seed", 0)?);
+    println!("{}", generator(size, &mut rng).trim_end_matches('\n'));
+    Ok(true)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_input::Source;
+    use std::path::Path;
+
+    fn example_day(budget: Duration) -> Day {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../2024/_1");
+        Day::new(Input::load(Source::Example(1), &dir).unwrap(), budget)
+    }
+
+    #[test]
+    fn test_statuses() {
+        let mut day = example_day(Duration::from_millis(200));
+        day.part(1, |i| i.lines().len() + 5);
+        day.part(2, |_| 30);
+        day.part(3, |_| -> u32 { panic!("Bad {}", 3) });
+        day.try_part(4, |_| "x".parse::<u32>());
+        day.part(5, |_| {
+            thread::sleep(Duration::from_secs(5));
+            0
+        });
+
+        let statuses: Vec<Status> = day.parts().iter().map(|p| p.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                Status::Ok,
+                Status::Wrong,
+                Status::Panic,
+                Status::Error,
+                Status::Timeout
+            ]
+        );
+        let p = &day.parts()[1];
+        assert_eq!(
+            (p.answer.as_deref(), p.expected.as_deref()),
+            (Some("30"), Some("31"))
+        );
+        assert_eq!(day.parts()[2].message.as_deref(), Some("Bad 3"));
+        assert_eq!(day.parts()[4].answer, None);
+        assert!(day.finish().is_err());
+    }
+
+    #[test]
+    fn test_variant() {
+        let variants: &[Variant<fn() -> u32>] = &[("fast", || 1), ("slow", || 2)];
+        let mut day = example_day(DEFAULT_TIMEOUT);
+        assert_eq!(day.variant(variants)(), 1);
+        day.variant = Some(("slow".to_string(), false));
+        assert_eq!(day.variant(&variants[..1])(), 1);
+        assert_eq!(day.variant(variants)(), 2);
+        assert!(day.finish().is_ok());
+
+        let mut day = example_day(DEFAULT_TIMEOUT);
+        day.variant = Some(("quick".to_string(), false));
+        day.variant(variants);
+        assert!(day.finish().is_err());
+    }
+
+    #[test]
+    fn test_report_written_per_part() {
+        let path = env::temp_dir().join(format!("aoc-runner-test-{}.tsv", std::process::id()));
+        let mut day = example_day(Duration::from_millis(200));
+        day.report = Some(path.clone());
+        day.part(1, |i| i.lines().len() + 5);
+        let written = fs::read_to_string(&path).unwrap();
+        assert_eq!(written.lines().count(), 1);
+        assert_eq!(
+            report::decode(written.lines().next().unwrap()),
+            Some(day.parts()[0].clone())
+        );
+
+        // Extras never run under `--report`.
+        day.extras(|_| panic!("Should be skipped"));
+        day.part(2, |_| 31);
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+        assert!(day.finish().is_ok());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_extras_contained() {
+        let mut day = example_day(Duration::from_millis(100));
+        day.part(2, |_| 31);
+        day.extras(|_| panic!("Broken rendering"));
+        day.extras(|_| thread::sleep(Duration::from_secs(5)));
+        assert_eq!(day.parts().len(), 1);
+        assert!(day.finish().is_ok());
+    }
+
+    #[test]
+    fn test_skipped_after_timeout() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        static RAN: AtomicBool = AtomicBool::new(false);
+        let mut day = example_day(Duration::from_millis(100));
+        day.part(1, |_| {
+            thread::sleep(Duration::from_secs(5));
+            0
+        });
+        day.part(2, |_| {
+            RAN.store(true, Ordering::SeqCst);
+            31
+        });
+        day.extras(|_| RAN.store(true, Ordering::SeqCst));
+
+        let statuses: Vec<Status> = day.parts().iter().map(|p| p.status).collect();
+        assert_eq!(statuses, vec![Status::Timeout, Status::Skipped]);
+        assert_eq!(day.parts()[1].duration, Duration::ZERO);
+        assert!(!RAN.load(Ordering::SeqCst));
+        assert!(day.finish().is_err());
+    }
+
+    #[test]
+    fn test_finish_ok() {
+        let mut day = example_day(DEFAULT_TIMEOUT);
+        day.part(2, |_| 31);
+        assert!(day.finish().is_ok());
+    }
+}
@@ -0,0 +1,289 @@
+//! Report formats: the line format a day hands to the runner, and the JSON
+//! and JUnit XML files the runner writes.
+
+use std::{fmt::Write, time::Duration};
+
+use crate::{PartReport, Status};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayReport {
+    pub year: u32,
+    pub day: u32,
+    /// Corpus name of the input, or where it came from otherwise.
+    pub input: String,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    /// `2024/_15`, as the day's directory is named.
+    pub fn name(&self) -> String {
+        format!("{}/_{}", self.year, self.day)
+    }
+}
+
+fn escape_field(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+fn unescape_field(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => out.push('\t'),
+            Some('n') => out.push('\n'),
+            Some(c) => out.push(c),
+            None => {}
+        }
+    }
+    out
+}
+
+/// One tab-separated line: part, status, duration in seconds, answer,
+/// expected answer and message, with absent values left empty.
+pub fn encode(p: &PartReport) -> String {
+    let opt = |s: &Option<String>| s.as_deref().map(escape_field).unwrap_or_default();
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}",
+        p.part,
+        p.status,
+        p.duration.as_secs_f64(),
+        opt(&p.answer),
+        opt(&p.expected),
+        opt(&p.message)
+    )
+}
+
+pub fn decode(line: &str) -> Option<PartReport> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [part, status, secs, answer, expected, message] = fields[..] else {
+        return None;
+    };
+    let opt = |s: &str| (!s.is_empty()).then(|| unescape_field(s));
+    Some(PartReport {
+        part: part.parse().ok()?,
+        status: *Status::ALL.iter().find(|s| s.to_string() == status)?,
+        duration: Duration::try_from_secs_f64(secs.parse().ok()?).ok()?,
+        answer: opt(answer),
+        expected: opt(expected),
+        message: opt(message),
+    })
+}
+
+fn json_string(s: Option<&str>) -> String {
+    let Some(s) = s else {
+        return "null".to_string();
+    };
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub fn to_json(days: &[DayReport]) -> String {
+    let mut out = String::from("{\n  \"days\": [");
+    for (i, d) in days.iter().enumerate() {
+        out.push_str(if i == 0 { "\n" } else { ",\n" });
+        write!(
+            out,
+            "    {{\"year\": {}, \"day\": {}, \"input\": {}, \"parts\": [",
+            d.year,
+            d.day,
+            json_string(Some(&d.input))
+        )
+        .unwrap();
+        for (j, p) in d.parts.iter().enumerate() {
+            out.push_str(if j == 0 { "\n" } else { ",\n" });
+            write!(
+                out,
+                "      {{\"part\": {}, \"status\": \"{}\", \"answer\": {}, \"expected\": {}, \
+                 \"duration_secs\": {}, \"message\": {}}}",
+                p.part,
+                p.status,
+                json_string(p.answer.as_deref()),
+                json_string(p.expected.as_deref()),
+                p.duration.as_secs_f64(),
+                json_string(p.message.as_deref())
+            )
+            .unwrap();
+        }
+        out.push_str(if d.parts.is_empty() { "]}" } else { "\n    ]}" });
+    }
+    out.push_str(if days.is_empty() {
+        "]\n}\n"
+    } else {
+        "\n  ]\n}\n"
+    });
+    out
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn is_error(s: &Status) -> bool {
+    !matches!(s, Status::Ok | Status::Wrong | Status::Skipped)
+}
+
+/// One `testsuite` per day and input, and one `testcase` per part. Wrong
+/// answers are failures; errors, panics and timeouts are errors, and parts
+/// skipped after a timeout are marked skipped.
+pub fn to_junit(days: &[DayReport]) -> String {
+    let all = days.iter().flat_map(|d| d.parts.iter());
+    let count = |f: fn(&Status) -> bool| all.clone().filter(|p| f(&p.status)).count();
+    let failures = count(|s| *s == Status::Wrong);
+    let errors = count(is_error);
+    let skipped = count(|s| *s == Status::Skipped);
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        out,
+        "<testsuites name=\"aoc\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\">",
+        all.clone().count(),
+        failures,
+        errors,
+        skipped
+    )
+    .unwrap();
+    for d in days {
+        let time: f64 = d.parts.iter().map(|p| p.duration.as_secs_f64()).sum();
+        let suite = xml_escape(&format!("{} {}", d.name(), d.input));
+        writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            suite,
+            d.parts.len(),
+            d.parts.iter().filter(|p| p.status == Status::Wrong).count(),
+            d.parts.iter().filter(|p| is_error(&p.status)).count(),
+            d.parts
+                .iter()
+                .filter(|p| p.status == Status::Skipped)
+                .count(),
+            time
+        )
+        .unwrap();
+        for p in d.parts.iter() {
+            write!(
+                out,
+                "    <testcase classname=\"{}\" name=\"part{}\" time=\"{:.3}\"",
+                suite,
+                p.part,
+                p.duration.as_secs_f64()
+            )
+            .unwrap();
+            let message = xml_escape(p.message.as_deref().unwrap_or_default());
+            match p.status {
+                Status::Ok => out.push_str(" />\n"),
+                Status::Wrong => writeln!(
+                    out,
+                    ">\n      <failure type=\"wrong\" message=\"expected {}, got {}\" />\n    </testcase>",
+                    xml_escape(p.expected.as_deref().unwrap_or_default()),
+                    xml_escape(p.answer.as_deref().unwrap_or_default())
+                )
+                .unwrap(),
+                Status::Skipped => writeln!(
+                    out,
+                    ">\n      <skipped message=\"{}\" />\n    </testcase>",
+                    message
+                )
+                .unwrap(),
+                status => writeln!(
+                    out,
+                    ">\n      <error type=\"{}\" message=\"{}\" />\n    </testcase>",
+                    status, message
+                )
+                .unwrap(),
+            }
+        }
+        out.push_str("  </testsuite>\n");
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(
+        part: usize,
+        status: Status,
+        answer: Option<&str>,
+        message: Option<&str>,
+    ) -> PartReport {
+        PartReport {
+            part,
+            status,
+            answer: answer.map(String::from),
+            expected: Some("42".to_string()),
+            duration: Duration::from_millis(1500),
+            message: message.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_line_roundtrip() {
+        let p = part(2, Status::Panic, None, Some("a\tb\nc\\d"));
+        assert_eq!(encode(&p), "2\tpanic\t1.5\t\t42\ta\\tb\\nc\\\\d");
+        assert_eq!(decode(&encode(&p)), Some(p));
+        assert_eq!(decode("1\tok"), None);
+    }
+
+    #[test]
+    fn test_json() {
+        let days = [DayReport {
+            year: 2024,
+            day: 3,
+            input: "input".to_string(),
+            parts: vec![part(1, Status::Wrong, Some("4\"1"), None)],
+        }];
+        assert_eq!(
+            to_json(&days),
+            "{\n  \"days\": [\n    {\"year\": 2024, \"day\": 3, \"input\": \"input\", \"parts\": [\n      \
+             {\"part\": 1, \"status\": \"wrong\", \"answer\": \"4\\\"1\", \"expected\": \"42\", \
+             \"duration_secs\": 1.5, \"message\": null}\n    ]}\n  ]\n}\n"
+        );
+        assert_eq!(to_json(&[]), "{\n  \"days\": []\n}\n");
+    }
+
+    #[test]
+    fn test_junit() {
+        let days = [DayReport {
+            year: 2024,
+            day: 17,
+            input: "slow".to_string(),
+            parts: vec![
+                part(1, Status::Ok, Some("42"), None),
+                part(2, Status::Timeout, None, Some("No <answer>")),
+                part(3, Status::Skipped, None, Some("An earlier part timed out")),
+            ],
+        }];
+        let xml = to_junit(&days);
+        assert!(xml.contains(
+            "<testsuites name=\"aoc\" tests=\"3\" failures=\"0\" errors=\"1\" skipped=\"1\">"
+        ));
+        assert!(
+            xml.contains("<testcase classname=\"2024/_17 slow\" name=\"part1\" time=\"1.500\" />")
+        );
+        assert!(xml.contains("<error type=\"timeout\" message=\"No &lt;answer&gt;\" />"));
+        assert!(xml.contains("<skipped message=\"An earlier part timed out\" />"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Simulating a deterministic system until it halts or repeats.
+//!
+//! A simulation is a start state and a step function; a step returning
+//! `None` halts it. Since the step is deterministic, the first repeated
+//! state closes a cycle that the simulation then follows forever, so any
+//! later state can be found by jumping around the cycle.
+//!
+//! [`brent`] and [`floyd`] detect the cycle while holding only a couple of
+//! states, at the cost of re-running the steps; [`hashed`] steps once but
+//! remembers every state.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// The states of a simulation, as the prefix before its cycle followed by
+/// one lap of the cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// Steps before the cycle is entered.
+    pub prefix: usize,
+    /// Steps per lap of the cycle, or 0 if the simulation halted.
+    pub len: usize,
+    /// The first `prefix + len` states: every state the simulation visits.
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    pub fn halted(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The repeating states, empty if the simulation halted.
+    pub fn lap(&self) -> &[S] {
+        &self.states[self.prefix..]
+    }
+
+    /// The state after `n` steps, or `None` if the simulation halted before.
+    pub fn nth(&self, n: usize) -> Option<&S> {
+        match self.states.get(n) {
+            Some(s) => Some(s),
+            None if self.halted() => None,
+            None => Some(&self.states[self.prefix + (n - self.prefix) % self.len]),
+        }
+    }
+}
+
+/// Every state until the simulation halts.
+fn run_out<S>(start: S, mut step: impl FnMut(&S) -> Option<S>) -> Cycle<S> {
+    let mut states = vec![start];
+    while let Some(s) = step(states.last().unwrap()) {
+        states.push(s);
+    }
+    Cycle {
+        prefix: states.len(),
+        len: 0,
+        states,
+    }
+}
+
+/// Collects the states once the cycle is known to start after `prefix`
+/// steps and last `len`.
+fn collect<S: Clone>(
+    start: S,
+    prefix: usize,
+    len: usize,
+    mut step: impl FnMut(&S) -> Option<S>,
+) -> Cycle<S> {
+    let mut states = vec![start];
+    for _ in 1..prefix + len {
+        let next = step(states.last().unwrap()).expect("Step is not deterministic");
+        states.push(next);
+    }
+    Cycle {
+        prefix,
+        len,
+        states,
+    }
+}
+
+/// Brent's algorithm: the hare runs ahead of a tortoise that teleports to
+/// it at every power of two, so the cycle length falls out of the first
+/// meeting.
+pub fn brent<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> Option<S>) -> Cycle<S> {
+    let (mut power, mut len) = (1, 1);
+    let mut tortoise = start.clone();
+    let Some(mut hare) = step(&start) else {
+        return run_out(start, step);
+    };
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        match step(&hare) {
+            Some(s) => hare = s,
+            None => return run_out(start, step),
+        }
+        len += 1;
+    }
+
+    // A tortoise `len` steps behind the hare meets it where the cycle starts.
+    let mut step = move |s: &S| step(s).expect("Step is not deterministic");
+    let (mut tortoise, mut hare) = (start.clone(), start.clone());
+    for _ in 0..len {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    collect(start, prefix, len, |s| Some(step(s)))
+}
+
+/// Floyd's algorithm: a hare at twice the tortoise's speed meets it
+/// somewhere on the cycle.
+pub fn floyd<S: Clone + Eq>(start: S, mut step: impl FnMut(&S) -> Option<S>) -> Cycle<S> {
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    loop {
+        let next = step(&hare).and_then(|h| step(&h));
+        let Some(next) = next else {
+            return run_out(start, step);
+        };
+        hare = next;
+        tortoise = step(&tortoise).expect("Step is not deterministic");
+        if tortoise == hare {
+            break;
+        }
+    }
+
+    let mut step = move |s: &S| step(s).expect("Step is not deterministic");
+    let mut tortoise = start.clone();
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+    let mut len = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        len += 1;
+    }
+    collect(start, prefix, len, |s| Some(step(s)))
+}
+
+/// Steps once, looking every state up among those seen before.
+pub fn hashed<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> Option<S>) -> Cycle<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut cur = Some(start);
+    while let Some(s) = cur {
+        if let Some(&prefix) = seen.get(&s) {
+            return Cycle {
+                prefix,
+                len: states.len() - prefix,
+                states,
+            };
+        }
+        seen.insert(s.clone(), states.len());
+        cur = step(&s);
+        states.push(s);
+    }
+    Cycle {
+        prefix: states.len(),
+        len: 0,
+        states,
+    }
+}
+
+/// The state after `n` steps of a simulation that never halts. Keeps only
+/// Brent's tortoise besides the current state, and once it meets the
+/// tortoise again skips every whole lap still ahead, so states that never
+/// repeat cost one comparison per step.
+pub fn nth<S: Clone + Eq>(start: S, n: usize, mut step: impl FnMut(&S) -> S) -> S {
+    let mut tortoise = start.clone();
+    let mut cur = start;
+    let (mut power, mut len) = (1, 0);
+    for t in 1..=n {
+        cur = step(&cur);
+        len += 1;
+        if cur == tortoise {
+            for _ in 0..(n - t) % len {
+                cur = step(&cur);
+            }
+            return cur;
+        }
+        if len == power {
+            tortoise = cur.clone();
+            power *= 2;
+            len = 0;
+        }
+    }
+    cur
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, ..., 9, then round 4..=9 forever.
+    fn rho(s: &u32) -> Option<u32> {
+        Some(if *s == 9 { 4 } else { s + 1 })
+    }
+
+    type Detector = fn(u32, fn(&u32) -> Option<u32>) -> Cycle<u32>;
+
+    const DETECTORS: [(&str, Detector); 3] = [
+        ("brent", |s, f| brent(s, f)),
+        ("floyd", |s, f| floyd(s, f)),
+        ("hashed", |s, f| hashed(s, f)),
+    ];
+
+    #[test]
+    fn test_rho() {
+        for (name, detect) in DETECTORS {
+            let c = detect(0, rho);
+            assert_eq!((c.prefix, c.len), (4, 6), "{}", name);
+            assert_eq!(c.states, (0..10).collect::<Vec<_>>(), "{}", name);
+            assert_eq!(c.lap(), &[4, 5, 6, 7, 8, 9]);
+            assert_eq!(c.nth(3), Some(&3));
+            assert_eq!(c.nth(10), Some(&4));
+            assert_eq!(c.nth(1_000_003), Some(&7));
+        }
+    }
+
+    #[test]
+    fn test_fixed_point_and_halt() {
+        for (name, detect) in DETECTORS {
+            let c = detect(5, |s| Some(*s));
+            assert_eq!((c.prefix, c.len, c.states), (0, 1, vec![5]), "{}", name);
+
+            let c = detect(0, |s| (*s < 3).then_some(s + 1));
+            assert!(c.halted());
+            assert_eq!(
+                (c.prefix, &c.states[..]),
+                (4, &[0, 1, 2, 3][..]),
+                "{}",
+                name
+            );
+            assert_eq!(c.nth(3), Some(&3));
+            assert_eq!(c.nth(4), None);
+            assert!(c.lap().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        // Pseudo-random maps on 0..m, each a rho of its own shape.
+        for m in 1..200u64 {
+            let f = move |s: &u64| Some((s * s + m / 3 + 1) % m);
+            let c = brent(0, f);
+            assert_eq!(c, floyd(0, f), "m = {}", m);
+            assert_eq!(c, hashed(0, f), "m = {}", m);
+        }
+    }
+
+    #[test]
+    fn test_nth() {
+        let step = |s: &u64| (s * s + 7) % 1009;
+        let naive = |n: usize| (0..n).fold(3, |s, _| step(&s));
+        for n in [0, 1, 2, 17, 100, 1000, 12345] {
+            assert_eq!(nth(3, n, step), naive(n), "n = {}", n);
+        }
+        assert_eq!(
+            nth(0u32, usize::MAX, |s| (s + 1) % 7),
+            (usize::MAX % 7) as u32
+        );
+    }
+}